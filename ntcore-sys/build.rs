@@ -1,66 +1,434 @@
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Checked in under src/, so building doesn't need libclang. See regenerate().
+const BINDINGS_FILE: &str = "bindings.rs";
+// The same bindings with the functions loaded through libloading, made from BINDINGS_FILE in
+// OUT_DIR for the runtime-load feature. See dynamic_bindings().
+#[cfg(feature = "runtime-load")]
+const DYNAMIC_BINDINGS_FILE: &str = "bindings_dynamic.rs";
+const HASH_PREFIX: &str = "// ntcoreffi headers fnv1a: ";
+
+// Where the maven headers/binary zips go if you unzip them by hand, relative to this crate.
+const LOCAL_DIR: &str = "ntcoreffi";
+// Where `vendored` looks for an allwpilib checkout if ALLWPILIB_DIR isn't set.
+#[cfg(feature = "vendored")]
+const LOCAL_ALLWPILIB_DIR: &str = "allwpilib";
+
+/// Prints a `cargo:` link line, except with `runtime-load`, where the only reason to look for
+/// ntcoreffi at build time is its headers for regenerate-bindings.
+fn link(key: &str, value: impl std::fmt::Display) {
+    if !cfg!(feature = "runtime-load") {
+        println!("cargo:{}={}", key, value);
+    }
+}
+
+/// A folder with ntcoreffi in it, either laid out like the maven zips unzipped on top of each
+/// other, or with the library somewhere under a platform folder like the binary zip has it.
+/// The headers zip is only needed to check or regenerate the bindings.
+fn link_ntcoreffi_dir(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let arch = match env::var("CARGO_CFG_TARGET_ARCH").unwrap().as_str() {
+        "aarch64" => "arm64",
+        "arm" => "arm32",
+        _ => "x86-64",
+    };
+    let platform = match env::var("CARGO_CFG_TARGET_OS").unwrap().as_str() {
+        "macos" => PathBuf::from("osx/universal"),
+        "windows" => Path::new("windows").join(arch),
+        _ => Path::new("linux").join(arch),
+    };
+    let lib_dir = [dir.to_path_buf(), dir.join(platform).join("shared")]
+        .into_iter()
+        .find(|d| has_library(d))
+        .ok_or_else(|| {
+            format!(
+                "no ntcoreffi library in {}, unzip the binary zip for your platform there",
+                dir.display()
+            )
+        })?;
+
+    link("rustc-link-search", lib_dir.display());
+    link("rustc-link-lib", "ntcoreffi");
+    println!("cargo:rerun-if-changed={}", dir.display());
+    Ok(vec![dir.to_path_buf()])
+}
+
+fn has_library(dir: &Path) -> bool {
+    ["libntcoreffi.so", "libntcoreffi.dylib", "ntcoreffi.lib"]
+        .iter()
+        .any(|name| dir.join(name).exists())
+}
+
+/// Asks pkg-config, then looks in the usual install prefixes.
+fn link_system() -> Option<Vec<PathBuf>> {
+    // Link flags get printed here instead of by pkg-config, so only the branch we pick prints any.
+    if let Ok(lib) = pkg_config::Config::new()
+        .cargo_metadata(false)
+        .probe("ntcoreffi")
+    {
+        for path in &lib.link_paths {
+            link("rustc-link-search", path.display());
+        }
+        for name in &lib.libs {
+            link("rustc-link-lib", name);
+        }
+        return Some(
+            lib.include_paths
+                .iter()
+                .flat_map(|p| [p.clone(), p.join("ntcoreffi")])
+                .collect(),
+        );
+    }
+    // cfg!(unix) would be the machine running the build, not the one we build for.
+    if env::var("CARGO_CFG_TARGET_FAMILY").is_ok_and(|f| f.split(',').any(|f| f == "unix")) {
+        for prefix in ["/usr/local", "/usr"] {
+            let include = Path::new(prefix).join("include/ntcoreffi");
+            let lib = Path::new(prefix).join("lib");
+            if has_library(&lib) {
+                link("rustc-link-search", lib.display());
+                link("rustc-link-lib", "ntcoreffi");
+                return Some(vec![include]);
+            }
+        }
+    }
+    None
+}
+
+/// Builds ntcore and what it needs out of an allwpilib checkout with cmake and links it in
+/// statically. There's no separate ntcoreffi there, the C API is part of ntcore itself.
+#[cfg(feature = "vendored")]
+fn link_vendored(manifest_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    println!("cargo:rerun-if-env-changed=ALLWPILIB_DIR");
+    let src = env::var_os("ALLWPILIB_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| manifest_dir.join(LOCAL_ALLWPILIB_DIR));
+    if !src.join("CMakeLists.txt").exists() {
+        return Err(format!(
+            "the vendored feature needs allwpilib sources, but {} isn't an allwpilib checkout. \
+             clone https://github.com/wpilibsuite/allwpilib there or set ALLWPILIB_DIR",
+            src.display()
+        ));
+    }
+
+    let dst = cmake::Config::new(&src)
+        .define("BUILD_SHARED_LIBS", "OFF")
+        .define("WITH_JAVA", "OFF")
+        .define("WITH_CSCORE", "OFF")
+        .define("WITH_WPIMATH", "OFF")
+        .define("WITH_WPILIB", "OFF")
+        .define("WITH_EXAMPLES", "OFF")
+        .define("WITH_TESTS", "OFF")
+        .define("WITH_GUI", "OFF")
+        .define("WITH_SIMULATION_MODULES", "OFF")
+        .define("WITH_PROTOBUF", "OFF")
+        .define("WITH_NTCORE", "ON")
+        .build();
+
+    link(
+        "rustc-link-search",
+        format!("native={}", dst.join("lib").display()),
+    );
+    // Order matters for static libs, users first.
+    for lib in ["ntcore", "wpinet", "wpiutil"] {
+        link("rustc-link-lib", format!("static={}", lib));
+    }
+    match env::var("CARGO_CFG_TARGET_OS").unwrap().as_str() {
+        "macos" => {
+            link("rustc-link-lib", "c++");
+            link("rustc-link-lib", "framework=CoreFoundation");
+        }
+        "windows" => {
+            for lib in [
+                "ws2_32", "iphlpapi", "userenv", "advapi32", "shell32", "ole32",
+            ] {
+                link("rustc-link-lib", lib);
+            }
+        }
+        _ => {
+            link("rustc-link-lib", "stdc++");
+            link("rustc-link-lib", "pthread");
+            link("rustc-link-lib", "dl");
+        }
+    }
+
+    let include = dst.join("include");
+    Ok(vec![
+        include.join("ntcore"),
+        include.join("wpinet"),
+        include.join("wpiutil"),
+    ])
+}
+
+#[cfg(not(feature = "vendored"))]
+fn link_vendored(_manifest_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    unreachable!("only called with the vendored feature")
+}
+
+/// Links ntcoreffi and returns the folders its headers are in. Tries, in order:
+/// NTCOREFFI_DIR, a vendored build (with the `vendored` feature), ntcore-sys/ntcoreffi,
+/// then pkg-config and the system include/lib folders.
+fn find_ntcoreffi(manifest_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    println!("cargo:rerun-if-env-changed=NTCOREFFI_DIR");
+    if let Some(dir) = env::var_os("NTCOREFFI_DIR") {
+        // Set on purpose, so don't quietly use something else if it's wrong.
+        return link_ntcoreffi_dir(Path::new(&dir)).map_err(|e| format!("NTCOREFFI_DIR: {}", e));
+    }
+
+    if cfg!(feature = "vendored") {
+        return link_vendored(manifest_dir);
+    }
+
+    let local = manifest_dir.join(LOCAL_DIR);
+    if local.exists() {
+        return link_ntcoreffi_dir(&local);
+    }
+    link_system().ok_or_else(|| {
+        format!(
+            "couldn't find ntcoreffi. Any of these work:\n\
+             - download the ntcoreffi headers and binary zips for your platform from \
+             https://frcmaven.wpi.edu/artifactory/release/edu/wpi/first/ntcoreffi/ \
+             and unzip both into {}\n\
+             - unzip them somewhere else and set NTCOREFFI_DIR to that folder\n\
+             - install it so pkg-config finds `ntcoreffi`\n\
+             - build it from allwpilib sources with the `vendored` feature (needs cmake)",
+            local.display()
+        )
+    })
+}
+
+/// ntcore_c.h and the generated types header next to it, from the first include folder that has
+/// them. Empty if we only found the library.
+fn find_headers(include_dirs: &[PathBuf]) -> Vec<PathBuf> {
+    include_dirs
+        .iter()
+        .find(|dir| dir.join("ntcore_c.h").exists())
+        .map(|dir| {
+            ["ntcore_c.h", "ntcore_c_types.h"]
+                .iter()
+                .map(|name| dir.join(name))
+                .filter(|path| path.exists())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// FNV-1a over the headers, so src/bindings.rs can say what it was generated from.
+fn hash_headers(headers: &[PathBuf]) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for header in headers {
+        println!("cargo:rerun-if-changed={}", header.display());
+        let data = fs::read(header).expect("couldn't read ntcoreffi header");
+        // Line endings change depending on how the zip got unzipped.
+        for byte in data.into_iter().filter(|b| *b != b'\r') {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// Bindings that don't match the library are crashes waiting to happen, so refuse to build with
+/// headers other than the ones the checked-in bindings came from. Bindings that don't record
+/// their headers only get a warning.
+#[cfg(not(feature = "regenerate-bindings"))]
+fn check_headers(manifest_dir: &Path, headers: &[PathBuf]) {
+    let bindings_path = manifest_dir.join("src").join(BINDINGS_FILE);
+    println!("cargo:rerun-if-changed={}", bindings_path.display());
+    if headers.is_empty() {
+        // Nothing to compare against, trust the library is the supported version.
+        return;
+    }
+    let bindings = fs::read_to_string(&bindings_path).expect("couldn't read src/bindings.rs");
+    let recorded = bindings
+        .lines()
+        .find_map(|line| line.strip_prefix(HASH_PREFIX))
+        .unwrap_or("unknown");
+    let found = hash_headers(headers);
+    if recorded == "unknown" {
+        // Nothing to compare with yet, so the usual unzip-and-build setup still has to work.
+        println!(
+            "cargo:warning=src/bindings.rs doesn't say which ntcoreffi headers it came from, so it \
+             can't be checked against the ones in {}. Build once with \
+             --features regenerate-bindings (needs libclang) and commit the new src/bindings.rs.",
+            headers[0].parent().unwrap().display()
+        );
+    } else if recorded != found {
+        panic!(
+            "\n\nthe ntcoreffi headers in {} ({}) aren't the ones src/bindings.rs was \
+             generated from ({}). Use the supported ntcoreffi version, or build with \
+             --features regenerate-bindings (needs libclang) and commit the new src/bindings.rs.\n\n",
+            headers[0].parent().unwrap().display(),
+            found,
+            recorded
+        );
+    }
+}
+
+/// Runs bindgen over the headers we found and writes the result to src/bindings.rs for committing,
+/// and to OUT_DIR for this build (as the runtime-load version too with that feature).
+#[cfg(feature = "regenerate-bindings")]
+fn regenerate(manifest_dir: &Path, include_dirs: &[PathBuf], headers: &[PathBuf]) {
+    if headers.is_empty() {
+        panic!(
+            "\n\nregenerate-bindings needs the ntcoreffi headers (ntcore_c.h), but only found the library\n\n"
+        );
+    }
+    let wrapper_path = manifest_dir.join("wrapper.h");
+    println!("cargo:rerun-if-changed={}", wrapper_path.display());
 
-fn main() {
-    // Uses shared library files, automatically built by gradle.
-    // Wish we could statically link this but unfortunately trying to build it results in a build failure,
-    // and its not part of the usual build task so I'm guessing thats just a fun feature
-    // #[cfg(target_os = "linux")]
-    // let libdir_relative_path = format!(
-    //     "../allwpilib/{}/build/libs/{}/shared/linuxx86-64/release",
-    //     "ntcoreffi", "ntcoreffi"
-    // );
-    // let libdir_path = PathBuf::from(libdir_relative_path)
-    //     .canonicalize()
-    //     .expect("cannot canonicalize path");
-    // println!("cargo:rustc-link-search={}", libdir_path.to_str().unwrap());
-
-    let ntcoreffi_dir = PathBuf::from("ntcoreffi").canonicalize().expect("bad path");
-    let ntcoreffi_dir_str = ntcoreffi_dir.to_str().unwrap();
-    // Windows inserts a mysterious "\\?\" before the paths, which causes
-    // clang to completely break. No idea why.
-    #[cfg(target_os = "windows")]
-    let ntcoreffi_dir_str = &ntcoreffi_dir_str[4..];
-    #[cfg(target_os = "windows")]
-    let dir = PathBuf::from(".").canonicalize().expect("bad path");
-    #[cfg(target_os = "windows")]
-    let dir_str = &dir.to_str().unwrap()[4..];
-
-    println!("cargo:rustc-link-search={}", ntcoreffi_dir_str);
-
-    println!("cargo:rustc-link-lib={}", "ntcoreffi");
-
-    let headers_path = PathBuf::from("wrapper.h").canonicalize().expect("bad path");
-
-    let headers_path_str = headers_path.to_str().expect("Path is not a valid string");
-
-    // The bindgen::Builder is the main entry point
-    // to bindgen, and lets you build up options for
-    // the resulting bindings.
     let bindings = bindgen::Builder::default()
-        // The input header we would like to generate
-        // bindings for.
-        .header(headers_path_str)
+        .header(wrapper_path.to_str().expect("Path is not a valid string"))
         // Tell cargo to invalidate the built crate whenever any of the
         // included header files changed.
         .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
         // makes the <> includes work properly. (why would they do this?)
-        .clang_args([
-            "-I",
-            ntcoreffi_dir_str,
-            #[cfg(target_os = "windows")]
-            "-I",
-            #[cfg(target_os = "windows")]
-            dir_str,
-        ])
-        // Finish the builder and generate the bindings.
+        .clang_args(
+            include_dirs
+                .iter()
+                .flat_map(|dir| ["-I".to_string(), dir.display().to_string()]),
+        )
+        // Leave out everything the C standard library headers drag in.
+        .allowlist_function("NT_.*|WPI_.*")
+        .allowlist_type("NT_.*|WPI_.*")
+        .allowlist_var("NT_.*")
         .generate()
-        // Unwrap the Result and panic on failure.
         .expect("Unable to generate bindings");
 
-    // Write the bindings to the $OUT_DIR/bindings.rs file.
-    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("bindings.rs");
-    bindings
-        .write_to_file(out_path)
-        .expect("Couldn't write bindings!");
+    let bindings = format!(
+        "// Generated by ntcore-sys/build.rs with the regenerate-bindings feature, don't edit.\n\
+         {}{}\n\n{}",
+        HASH_PREFIX,
+        hash_headers(headers),
+        bindings
+    );
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join(BINDINGS_FILE);
+    fs::write(&out_path, &bindings).expect("Couldn't write bindings!");
+    fs::write(manifest_dir.join("src").join(BINDINGS_FILE), &bindings)
+        .expect("Couldn't write src/bindings.rs!");
+    println!("cargo:warning=regenerated src/bindings.rs, commit it");
+    #[cfg(feature = "runtime-load")]
+    dynamic_bindings(&bindings);
+}
+
+/// Turns the `extern "C"` block of `bindings` into what bindgen's `dynamic_library_name("ntcoreffi")`
+/// makes: an `ntcoreffi` struct of function pointers loaded with libloading (all of them, or
+/// loading fails). On top of that, free functions with the same names as the linked ones call
+/// through the library src/runtime.rs loaded, so code using the crate doesn't change.
+/// Everything else (types, constants) is kept as it is. Written to OUT_DIR, nothing to commit.
+#[cfg(feature = "runtime-load")]
+fn dynamic_bindings(bindings: &str) {
+    let file = syn::parse_file(bindings).expect("src/bindings.rs doesn't parse");
+    let mut items = Vec::new();
+    let mut functions = Vec::new();
+    for item in file.items {
+        match item {
+            syn::Item::ForeignMod(block) => {
+                for item in block.items {
+                    match item {
+                        syn::ForeignItem::Fn(function) => functions.push(function.sig),
+                        other => panic!(
+                            "only functions can be loaded at runtime, not {}",
+                            quote::quote!(#other)
+                        ),
+                    }
+                }
+            }
+            other => items.push(other),
+        }
+    }
+
+    let names: Vec<_> = functions.iter().map(|sig| &sig.ident).collect();
+    let symbols = names
+        .iter()
+        .map(|name| syn::parse_str::<syn::LitByteStr>(&format!("b\"{}\\0\"", name)).unwrap());
+    let fields = functions.iter().map(|sig| {
+        let (name, inputs, output) = (&sig.ident, &sig.inputs, &sig.output);
+        quote::quote! { pub #name: unsafe extern "C" fn(#inputs) #output }
+    });
+    let methods = functions.iter().map(|sig| {
+        let (name, inputs, output) = (&sig.ident, &sig.inputs, &sig.output);
+        let args = arg_names(sig);
+        quote::quote! {
+            pub unsafe fn #name(&self, #inputs) #output {
+                unsafe { (self.#name)(#(#args),*) }
+            }
+        }
+    });
+    let wrappers = functions.iter().map(|sig| {
+        let (name, inputs, output) = (&sig.ident, &sig.inputs, &sig.output);
+        let args = arg_names(sig);
+        quote::quote! {
+            pub unsafe fn #name(#inputs) #output {
+                unsafe { crate::runtime::library().#name(#(#args),*) }
+            }
+        }
+    });
+
+    let dynamic: syn::File = syn::parse_quote! {
+        #(#items)*
+
+        pub struct ntcoreffi {
+            __library: ::libloading::Library,
+            #(#fields,)*
+        }
+        impl ntcoreffi {
+            pub unsafe fn new<P>(path: P) -> Result<Self, ::libloading::Error>
+            where
+                P: AsRef<::std::ffi::OsStr>,
+            {
+                let library = unsafe { ::libloading::Library::new(path) }?;
+                unsafe { Self::from_library(library) }
+            }
+            pub unsafe fn from_library<L>(library: L) -> Result<Self, ::libloading::Error>
+            where
+                L: Into<::libloading::Library>,
+            {
+                let __library = library.into();
+                #(let #names = unsafe { __library.get(#symbols) }.map(|sym| *sym)?;)*
+                Ok(ntcoreffi { __library, #(#names),* })
+            }
+            #(#methods)*
+        }
+
+        #(#wrappers)*
+    };
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join(DYNAMIC_BINDINGS_FILE);
+    fs::write(out_path, prettyplease::unparse(&dynamic)).expect("Couldn't write bindings!");
+}
+
+#[cfg(feature = "runtime-load")]
+fn arg_names(sig: &syn::Signature) -> impl Iterator<Item = &syn::Pat> {
+    sig.inputs.iter().filter_map(|arg| match arg {
+        syn::FnArg::Typed(arg) => Some(&*arg.pat),
+        syn::FnArg::Receiver(_) => None,
+    })
+}
+
+fn main() {
+    // Absolute already, so no canonicalize and none of the "\\?\" prefixes it adds on Windows,
+    // which clang can't handle.
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    // Nothing gets linked with `runtime-load`, the program finds the library when it starts.
+    // Without regenerate-bindings there's nothing to find at build time either.
+    if cfg!(feature = "runtime-load") && !cfg!(feature = "regenerate-bindings") {
+        let bindings_path = manifest_dir.join("src").join(BINDINGS_FILE);
+        println!("cargo:rerun-if-changed={}", bindings_path.display());
+        #[cfg(feature = "runtime-load")]
+        dynamic_bindings(
+            &fs::read_to_string(&bindings_path).expect("couldn't read src/bindings.rs"),
+        );
+        return;
+    }
+
+    let include_dirs = match find_ntcoreffi(&manifest_dir) {
+        Ok(dirs) => dirs,
+        Err(e) => panic!("\n\n{}\n\n", e),
+    };
+    let headers = find_headers(&include_dirs);
+
+    #[cfg(feature = "regenerate-bindings")]
+    regenerate(&manifest_dir, &include_dirs, &headers);
+    #[cfg(not(feature = "regenerate-bindings"))]
+    check_headers(&manifest_dir, &headers);
 }
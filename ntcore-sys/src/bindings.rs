@@ -0,0 +1,400 @@
+// ntcore_c.h bindings for ntcoreffi 2025.3.2.
+// Written out by hand in bindgen's style for the parts we use, since the build doesn't run bindgen
+// anymore. Build with --features regenerate-bindings to replace this with the full thing, which
+// also records the hash below.
+// ntcoreffi headers fnv1a: unknown
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct WPI_String {
+    pub str_: *const ::std::os::raw::c_char,
+    pub len: usize,
+}
+pub type NT_Bool = ::std::os::raw::c_int;
+pub type NT_Handle = ::std::os::raw::c_uint;
+pub type NT_ConnectionDataLogger = NT_Handle;
+pub type NT_DataLogger = NT_Handle;
+pub type NT_Entry = NT_Handle;
+pub type NT_Inst = NT_Handle;
+pub type NT_Listener = NT_Handle;
+pub type NT_ListenerPoller = NT_Handle;
+pub type NT_MultiSubscriber = NT_Handle;
+pub type NT_Topic = NT_Handle;
+pub type NT_Subscriber = NT_Handle;
+pub type NT_Publisher = NT_Handle;
+
+pub const NT_Type_NT_UNASSIGNED: NT_Type = 0;
+pub const NT_Type_NT_BOOLEAN: NT_Type = 1;
+pub const NT_Type_NT_DOUBLE: NT_Type = 2;
+pub const NT_Type_NT_STRING: NT_Type = 4;
+pub const NT_Type_NT_RAW: NT_Type = 8;
+pub const NT_Type_NT_BOOLEAN_ARRAY: NT_Type = 16;
+pub const NT_Type_NT_DOUBLE_ARRAY: NT_Type = 32;
+pub const NT_Type_NT_STRING_ARRAY: NT_Type = 64;
+pub const NT_Type_NT_RPC: NT_Type = 128;
+pub const NT_Type_NT_INTEGER: NT_Type = 256;
+pub const NT_Type_NT_FLOAT: NT_Type = 512;
+pub const NT_Type_NT_INTEGER_ARRAY: NT_Type = 1024;
+pub const NT_Type_NT_FLOAT_ARRAY: NT_Type = 2048;
+pub type NT_Type = ::std::os::raw::c_uint;
+
+pub const NT_NetworkMode_NT_NET_MODE_NONE: NT_NetworkMode = 0;
+pub const NT_NetworkMode_NT_NET_MODE_SERVER: NT_NetworkMode = 1;
+pub const NT_NetworkMode_NT_NET_MODE_CLIENT3: NT_NetworkMode = 2;
+pub const NT_NetworkMode_NT_NET_MODE_CLIENT4: NT_NetworkMode = 4;
+pub const NT_NetworkMode_NT_NET_MODE_LOCAL: NT_NetworkMode = 16;
+pub type NT_NetworkMode = ::std::os::raw::c_uint;
+
+pub const NT_EventFlags_NT_EVENT_NONE: NT_EventFlags = 0;
+pub const NT_EventFlags_NT_EVENT_IMMEDIATE: NT_EventFlags = 1;
+pub const NT_EventFlags_NT_EVENT_CONNECTED: NT_EventFlags = 2;
+pub const NT_EventFlags_NT_EVENT_DISCONNECTED: NT_EventFlags = 4;
+pub const NT_EventFlags_NT_EVENT_CONNECTION: NT_EventFlags = 6;
+pub const NT_EventFlags_NT_EVENT_PUBLISH: NT_EventFlags = 8;
+pub const NT_EventFlags_NT_EVENT_UNPUBLISH: NT_EventFlags = 16;
+pub const NT_EventFlags_NT_EVENT_PROPERTIES: NT_EventFlags = 32;
+pub const NT_EventFlags_NT_EVENT_TOPIC: NT_EventFlags = 56;
+pub const NT_EventFlags_NT_EVENT_VALUE_REMOTE: NT_EventFlags = 64;
+pub const NT_EventFlags_NT_EVENT_VALUE_LOCAL: NT_EventFlags = 128;
+pub const NT_EventFlags_NT_EVENT_VALUE_ALL: NT_EventFlags = 192;
+pub const NT_EventFlags_NT_EVENT_LOGMESSAGE: NT_EventFlags = 256;
+pub const NT_EventFlags_NT_EVENT_TIMESYNC: NT_EventFlags = 512;
+pub type NT_EventFlags = ::std::os::raw::c_uint;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct NT_Value {
+    pub type_: NT_Type,
+    pub last_change: i64,
+    pub server_time: i64,
+    pub data: NT_Value__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union NT_Value__bindgen_ty_1 {
+    pub v_boolean: NT_Bool,
+    pub v_int: i64,
+    pub v_float: f32,
+    pub v_double: f64,
+    pub v_string: WPI_String,
+    pub v_raw: NT_Value__bindgen_ty_1__bindgen_ty_1,
+    pub arr_boolean: NT_Value__bindgen_ty_1__bindgen_ty_2,
+    pub arr_double: NT_Value__bindgen_ty_1__bindgen_ty_3,
+    pub arr_float: NT_Value__bindgen_ty_1__bindgen_ty_4,
+    pub arr_int: NT_Value__bindgen_ty_1__bindgen_ty_5,
+    pub arr_string: NT_Value__bindgen_ty_1__bindgen_ty_6,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NT_Value__bindgen_ty_1__bindgen_ty_1 {
+    pub data: *mut u8,
+    pub size: usize,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NT_Value__bindgen_ty_1__bindgen_ty_2 {
+    pub arr: *mut NT_Bool,
+    pub size: usize,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NT_Value__bindgen_ty_1__bindgen_ty_3 {
+    pub arr: *mut f64,
+    pub size: usize,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NT_Value__bindgen_ty_1__bindgen_ty_4 {
+    pub arr: *mut f32,
+    pub size: usize,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NT_Value__bindgen_ty_1__bindgen_ty_5 {
+    pub arr: *mut i64,
+    pub size: usize,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NT_Value__bindgen_ty_1__bindgen_ty_6 {
+    pub arr: *mut WPI_String,
+    pub size: usize,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NT_TopicInfo {
+    pub topic: NT_Topic,
+    pub name: WPI_String,
+    pub type_: NT_Type,
+    pub type_str: WPI_String,
+    pub properties: WPI_String,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NT_ConnectionInfo {
+    pub remote_id: WPI_String,
+    pub remote_ip: WPI_String,
+    pub remote_port: ::std::os::raw::c_uint,
+    pub last_update: u64,
+    pub protocol_version: ::std::os::raw::c_uint,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct NT_ValueEventData {
+    pub topic: NT_Topic,
+    pub subentry: NT_Handle,
+    pub value: NT_Value,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NT_LogMessage {
+    pub level: ::std::os::raw::c_uint,
+    pub filename: WPI_String,
+    pub line: ::std::os::raw::c_uint,
+    pub message: WPI_String,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NT_TimeSyncEventData {
+    pub serverTimeOffset: i64,
+    pub rtt2: i64,
+    pub valid: NT_Bool,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct NT_Event {
+    pub listener: NT_Handle,
+    pub flags: ::std::os::raw::c_uint,
+    pub data: NT_Event__bindgen_ty_1,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union NT_Event__bindgen_ty_1 {
+    pub connInfo: NT_ConnectionInfo,
+    pub topicInfo: NT_TopicInfo,
+    pub valueData: NT_ValueEventData,
+    pub logMessage: NT_LogMessage,
+    pub timeSyncData: NT_TimeSyncEventData,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NT_PubSubOptions {
+    pub structSize: ::std::os::raw::c_uint,
+    pub pollStorage: ::std::os::raw::c_uint,
+    pub periodic: f64,
+    pub excludePublisher: NT_Publisher,
+    pub sendAll: NT_Bool,
+    pub topicsOnly: NT_Bool,
+    pub prefixMatch: NT_Bool,
+    pub keepDuplicates: NT_Bool,
+    pub disableRemote: NT_Bool,
+    pub disableLocal: NT_Bool,
+    pub excludeSelf: NT_Bool,
+    pub hidden: NT_Bool,
+}
+pub type NT_ListenerCallback = ::std::option::Option<
+    unsafe extern "C" fn(data: *mut ::std::os::raw::c_void, event: *const NT_Event),
+>;
+
+unsafe extern "C" {
+    pub fn WPI_FreeString(wpiString: *const WPI_String);
+    pub fn WPI_FreeStringArray(wpiStringArray: *const WPI_String, arrLength: usize);
+
+    pub fn NT_GetDefaultInstance() -> NT_Inst;
+    pub fn NT_CreateInstance() -> NT_Inst;
+    pub fn NT_DestroyInstance(inst: NT_Inst);
+    pub fn NT_GetEntry(inst: NT_Inst, name: *const WPI_String) -> NT_Entry;
+    pub fn NT_GetEntryName(entry: NT_Entry, name: *mut WPI_String);
+    pub fn NT_GetEntryType(entry: NT_Entry) -> NT_Type;
+    pub fn NT_GetEntryLastChange(entry: NT_Entry) -> u64;
+    pub fn NT_GetEntryValue(entry: NT_Entry, value: *mut NT_Value);
+    pub fn NT_DisposeValue(value: *mut NT_Value);
+    pub fn NT_Now() -> i64;
+
+    pub fn NT_GetTopics(
+        inst: NT_Inst,
+        prefix: *const WPI_String,
+        types: ::std::os::raw::c_uint,
+        count: *mut usize,
+    ) -> *mut NT_Topic;
+    pub fn NT_GetTopicInfos(
+        inst: NT_Inst,
+        prefix: *const WPI_String,
+        types: ::std::os::raw::c_uint,
+        count: *mut usize,
+    ) -> *mut NT_TopicInfo;
+    pub fn NT_DisposeTopicInfoArray(arr: *mut NT_TopicInfo, count: usize);
+    pub fn NT_GetTopic(inst: NT_Inst, name: *const WPI_String) -> NT_Topic;
+    pub fn NT_GetTopicName(topic: NT_Topic, name: *mut WPI_String);
+    pub fn NT_GetTopicType(topic: NT_Topic) -> NT_Type;
+    pub fn NT_GetTopicTypeString(topic: NT_Topic, type_: *mut WPI_String);
+    pub fn NT_GetTopicExists(handle: NT_Handle) -> NT_Bool;
+
+    pub fn NT_Subscribe(
+        topic: NT_Topic,
+        type_: NT_Type,
+        typeStr: *const WPI_String,
+        options: *const NT_PubSubOptions,
+    ) -> NT_Subscriber;
+    pub fn NT_Unsubscribe(sub: NT_Subscriber);
+    pub fn NT_Publish(
+        topic: NT_Topic,
+        type_: NT_Type,
+        typeStr: *const WPI_String,
+        options: *const NT_PubSubOptions,
+    ) -> NT_Publisher;
+    pub fn NT_PublishEx(
+        topic: NT_Topic,
+        type_: NT_Type,
+        typeStr: *const WPI_String,
+        properties: *const WPI_String,
+        options: *const NT_PubSubOptions,
+    ) -> NT_Publisher;
+    pub fn NT_Unpublish(pubentry: NT_Handle);
+    pub fn NT_SubscribeMultiple(
+        inst: NT_Inst,
+        prefixes: *const WPI_String,
+        prefixes_len: usize,
+        options: *const NT_PubSubOptions,
+    ) -> NT_MultiSubscriber;
+    pub fn NT_UnsubscribeMultiple(sub: NT_MultiSubscriber);
+
+    pub fn NT_AddListener(
+        handle: NT_Handle,
+        mask: ::std::os::raw::c_uint,
+        data: *mut ::std::os::raw::c_void,
+        callback: NT_ListenerCallback,
+    ) -> NT_Listener;
+    pub fn NT_CreateListenerPoller(inst: NT_Inst) -> NT_ListenerPoller;
+    pub fn NT_DestroyListenerPoller(poller: NT_ListenerPoller);
+    pub fn NT_ReadListenerQueue(poller: NT_ListenerPoller, len: *mut usize) -> *mut NT_Event;
+    pub fn NT_RemoveListener(listener: NT_Listener);
+    pub fn NT_AddPolledListenerSingle(
+        poller: NT_ListenerPoller,
+        prefix: *const WPI_String,
+        mask: ::std::os::raw::c_uint,
+    ) -> NT_Listener;
+    pub fn NT_AddPolledListenerMultiple(
+        poller: NT_ListenerPoller,
+        prefixes: *const WPI_String,
+        prefixes_len: usize,
+        mask: ::std::os::raw::c_uint,
+    ) -> NT_Listener;
+    pub fn NT_AddPolledListener(
+        poller: NT_ListenerPoller,
+        handle: NT_Handle,
+        mask: ::std::os::raw::c_uint,
+    ) -> NT_Listener;
+    pub fn NT_DisposeEventArray(arr: *mut NT_Event, count: usize);
+    pub fn NT_DisposeEvent(event: *mut NT_Event);
+
+    pub fn NT_GetNetworkMode(inst: NT_Inst) -> ::std::os::raw::c_uint;
+    pub fn NT_StartLocal(inst: NT_Inst);
+    pub fn NT_StopLocal(inst: NT_Inst);
+    pub fn NT_StartServer(
+        inst: NT_Inst,
+        persist_filename: *const WPI_String,
+        listen_address: *const WPI_String,
+        port3: ::std::os::raw::c_uint,
+        port4: ::std::os::raw::c_uint,
+    );
+    pub fn NT_StopServer(inst: NT_Inst);
+    pub fn NT_StartClient4(inst: NT_Inst, identity: *const WPI_String);
+    pub fn NT_StopClient(inst: NT_Inst);
+    pub fn NT_SetServer(
+        inst: NT_Inst,
+        server_name: *const WPI_String,
+        port: ::std::os::raw::c_uint,
+    );
+    pub fn NT_SetServerTeam(
+        inst: NT_Inst,
+        team: ::std::os::raw::c_uint,
+        port: ::std::os::raw::c_uint,
+    );
+    pub fn NT_Flush(inst: NT_Inst);
+    pub fn NT_FlushLocal(inst: NT_Inst);
+    pub fn NT_IsConnected(inst: NT_Inst) -> NT_Bool;
+
+    pub fn NT_FreeBooleanArray(v_boolean: *mut NT_Bool);
+    pub fn NT_FreeIntegerArray(v_int: *mut i64);
+    pub fn NT_FreeFloatArray(v_float: *mut f32);
+    pub fn NT_FreeDoubleArray(v_double: *mut f64);
+
+    pub fn NT_SetBoolean(pubentry: NT_Handle, time: i64, value: NT_Bool) -> NT_Bool;
+    pub fn NT_SetInteger(pubentry: NT_Handle, time: i64, value: i64) -> NT_Bool;
+    pub fn NT_SetFloat(pubentry: NT_Handle, time: i64, value: f32) -> NT_Bool;
+    pub fn NT_SetDouble(pubentry: NT_Handle, time: i64, value: f64) -> NT_Bool;
+    pub fn NT_SetString(pubentry: NT_Handle, time: i64, value: *const WPI_String) -> NT_Bool;
+    pub fn NT_SetRaw(pubentry: NT_Handle, time: i64, value: *const u8, len: usize) -> NT_Bool;
+    pub fn NT_SetBooleanArray(
+        pubentry: NT_Handle,
+        time: i64,
+        value: *const NT_Bool,
+        len: usize,
+    ) -> NT_Bool;
+    pub fn NT_SetIntegerArray(
+        pubentry: NT_Handle,
+        time: i64,
+        value: *const i64,
+        len: usize,
+    ) -> NT_Bool;
+    pub fn NT_SetFloatArray(
+        pubentry: NT_Handle,
+        time: i64,
+        value: *const f32,
+        len: usize,
+    ) -> NT_Bool;
+    pub fn NT_SetDoubleArray(
+        pubentry: NT_Handle,
+        time: i64,
+        value: *const f64,
+        len: usize,
+    ) -> NT_Bool;
+    pub fn NT_SetStringArray(
+        pubentry: NT_Handle,
+        time: i64,
+        value: *const WPI_String,
+        len: usize,
+    ) -> NT_Bool;
+
+    pub fn NT_GetBoolean(subentry: NT_Handle, defaultValue: NT_Bool) -> NT_Bool;
+    pub fn NT_GetInteger(subentry: NT_Handle, defaultValue: i64) -> i64;
+    pub fn NT_GetFloat(subentry: NT_Handle, defaultValue: f32) -> f32;
+    pub fn NT_GetDouble(subentry: NT_Handle, defaultValue: f64) -> f64;
+    pub fn NT_GetString(
+        subentry: NT_Handle,
+        defaultValue: *const WPI_String,
+        value: *mut WPI_String,
+    );
+    pub fn NT_GetBooleanArray(
+        subentry: NT_Handle,
+        defaultValue: *const NT_Bool,
+        defaultValueLen: usize,
+        len: *mut usize,
+    ) -> *mut NT_Bool;
+    pub fn NT_GetIntegerArray(
+        subentry: NT_Handle,
+        defaultValue: *const i64,
+        defaultValueLen: usize,
+        len: *mut usize,
+    ) -> *mut i64;
+    pub fn NT_GetFloatArray(
+        subentry: NT_Handle,
+        defaultValue: *const f32,
+        defaultValueLen: usize,
+        len: *mut usize,
+    ) -> *mut f32;
+    pub fn NT_GetDoubleArray(
+        subentry: NT_Handle,
+        defaultValue: *const f64,
+        defaultValueLen: usize,
+        len: *mut usize,
+    ) -> *mut f64;
+    pub fn NT_GetStringArray(
+        subentry: NT_Handle,
+        defaultValue: *const WPI_String,
+        defaultValueLen: usize,
+        len: *mut usize,
+    ) -> *mut WPI_String;
+}
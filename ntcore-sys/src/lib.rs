@@ -1,4 +1,18 @@
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
+// The wrappers runtime-load makes are as unsafe as the C functions they stand in for.
+#![allow(clippy::missing_safety_doc)]
+#[cfg(not(any(feature = "regenerate-bindings", feature = "runtime-load")))]
+include!("bindings.rs");
+#[cfg(all(feature = "regenerate-bindings", not(feature = "runtime-load")))]
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+// Same functions, but called through the `ntcoreffi` struct build.rs makes out of bindings.rs,
+// loaded from the library when the program starts instead of linked in.
+#[cfg(feature = "runtime-load")]
+include!(concat!(env!("OUT_DIR"), "/bindings_dynamic.rs"));
+#[cfg(feature = "runtime-load")]
+mod runtime;
+#[cfg(feature = "runtime-load")]
+pub use runtime::{library_file_name, load, search_paths};
@@ -0,0 +1,79 @@
+use std::{env, path::PathBuf, sync::OnceLock};
+
+use libloading::Library;
+
+use crate::ntcoreffi;
+
+// Never unloaded, the function pointers point into it.
+static LOADED: OnceLock<ntcoreffi> = OnceLock::new();
+
+/// What the library is called on this platform, like "libntcoreffi.so".
+pub fn library_file_name() -> String {
+    libloading::library_filename("ntcoreffi")
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Where `load` looks, in order: NTCOREFFI_PATH (the library or the folder it's in), next to
+/// the executable, then the bare name so the system's search path (LD_LIBRARY_PATH etc.) gets a go.
+pub fn search_paths() -> Vec<PathBuf> {
+    let name = library_file_name();
+    let mut paths = Vec::new();
+    if let Some(path) = env::var_os("NTCOREFFI_PATH").map(PathBuf::from) {
+        paths.push(if path.is_dir() {
+            path.join(&name)
+        } else {
+            path
+        });
+    }
+    if let Some(dir) = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|d| d.to_path_buf()))
+    {
+        paths.push(dir.join(&name));
+    }
+    paths.push(PathBuf::from(name));
+    paths
+}
+
+/// Finds and loads ntcoreffi. Does nothing if it's already loaded, and can be called again after
+/// failing (e.g. once the user put the library somewhere).
+/// The `NT_` functions load it themselves the first time, and panic if they can't.
+pub fn load() -> Result<(), String> {
+    if LOADED.get().is_some() {
+        return Ok(());
+    }
+    let mut tried = Vec::new();
+    for path in search_paths() {
+        // Runs the library's initializers, which for ntcoreffi is fine.
+        let library = match unsafe { Library::new(&path) } {
+            Ok(library) => library,
+            Err(e) => {
+                tried.push(format!("{}: {}", path.display(), e));
+                continue;
+            }
+        };
+        let loaded = unsafe { ntcoreffi::from_library(library) }.map_err(|e| {
+            format!(
+                "{} doesn't have everything we need, is it the right ntcoreffi version? {}",
+                path.display(),
+                e
+            )
+        })?;
+        // Someone else might have won the race, either way it's loaded now.
+        let _ = LOADED.set(loaded);
+        return Ok(());
+    }
+    Err(format!(
+        "couldn't load {}. Tried:\n{}",
+        library_file_name(),
+        tried.join("\n")
+    ))
+}
+
+pub(crate) fn library() -> &'static ntcoreffi {
+    if let Err(e) = load() {
+        panic!("ntcoreffi isn't loaded: {}", e);
+    }
+    LOADED.get().unwrap()
+}
@@ -3,4 +3,5 @@
 // nice job, guys.
 #include <stddef.h>
 
-#include "ntcoreffi/ntcore_c.h"
+// Found through the include folders build.rs passes in.
+#include <ntcore_c.h>
@@ -0,0 +1,70 @@
+use std::{fs, path::Path};
+
+use serde::Deserialize;
+
+/// WPILib's AprilTag field layout JSON, the same format `AprilTagFieldLayout` loads.
+#[derive(Debug, Deserialize)]
+pub struct AprilTagFieldLayout {
+    pub tags: Vec<AprilTag>,
+    pub field: FieldSize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AprilTag {
+    #[serde(rename = "ID")]
+    pub id: i64,
+    pub pose: Pose3d,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FieldSize {
+    pub length: f64,
+    pub width: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Pose3d {
+    pub translation: Translation3d,
+    pub rotation: Rotation3d,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Translation3d {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Rotation3d {
+    pub quaternion: Quaternion,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl AprilTagFieldLayout {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text).map_err(|e| format!("bad layout {}: {}", path.display(), e))
+    }
+
+    pub fn tag(&self, id: i64) -> Option<&AprilTag> {
+        self.tags.iter().find(|t| t.id == id)
+    }
+}
+
+impl Quaternion {
+    /// Rotation around the field's z axis, in radians.
+    pub fn yaw(&self) -> f64 {
+        (2.0 * (self.w * self.z + self.x * self.y))
+            .atan2(1.0 - 2.0 * (self.y * self.y + self.z * self.z))
+    }
+}
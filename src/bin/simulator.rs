@@ -0,0 +1,214 @@
+//! Fake robot for working on the dashboard without one. Runs an NT server on this computer and
+//! publishes values from a scenario file, see sim/match.json.
+//!
+//! cargo run --bin simulator -- [scenario file] [port]
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, thread,
+    time::{Duration, Instant},
+};
+
+use robot_ui_rs::{
+    data_source::DataSource, nt_instance::NtInstance, nt_paths, nt_util::NTValueType,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+const DEFAULT_SCENARIO: &str = "sim/match.json";
+const DEFAULT_PORT: u32 = 5810;
+// Same as the robot's main loop.
+const TICK: Duration = Duration::from_millis(20);
+
+#[derive(Deserialize)]
+struct Scenario {
+    /// Seconds. The scenario starts over after this unless `once` is set.
+    length: f64,
+    #[serde(default)]
+    once: bool,
+    /// Chooser tables to play the robot side of: whatever the dashboard selects becomes active.
+    #[serde(default)]
+    choosers: Vec<String>,
+    /// Topic path -> values over time.
+    topics: BTreeMap<String, Track>,
+}
+
+#[derive(Deserialize)]
+struct Track {
+    /// NT type, for when it can't be guessed from the JSON ("int64", "float", "int64[]", ...).
+    #[serde(rename = "type", default)]
+    type_name: String,
+    /// Blend numbers and number arrays between keys instead of jumping.
+    /// Two keys at the same time still make a jump.
+    #[serde(default)]
+    smooth: bool,
+    /// [seconds, value] pairs, in order. Nothing is published before the first one.
+    keys: Vec<(f64, Value)>,
+}
+
+fn guess_type(value: &Value) -> &'static str {
+    match value {
+        Value::Bool(_) => "boolean",
+        Value::String(_) => "string",
+        Value::Array(arr) => match arr.first() {
+            Some(Value::Bool(_)) => "boolean[]",
+            Some(Value::String(_)) => "string[]",
+            _ => "double[]",
+        },
+        _ => "double",
+    }
+}
+
+fn to_nt(value: &Value, type_name: &str) -> Option<NTValueType> {
+    let numbers = || -> Option<Vec<f64>> { value.as_array()?.iter().map(Value::as_f64).collect() };
+    Some(match type_name {
+        "boolean" => NTValueType::Boolean(value.as_bool()?),
+        "string" => NTValueType::String(value.as_str()?.to_string()),
+        "double" => NTValueType::Double(value.as_f64()?),
+        "float" => NTValueType::Float(value.as_f64()? as f32),
+        "int64" => NTValueType::Integer(value.as_f64()?.round() as i32),
+        "double[]" => NTValueType::DoubleArray(numbers()?),
+        "float[]" => NTValueType::FloatArray(numbers()?.iter().map(|n| *n as f32).collect()),
+        "int64[]" => {
+            NTValueType::IntegerArray(numbers()?.iter().map(|n| n.round() as i32).collect())
+        }
+        "boolean[]" => NTValueType::BooleanArray(
+            value
+                .as_array()?
+                .iter()
+                .map(Value::as_bool)
+                .collect::<Option<_>>()?,
+        ),
+        "string[]" => NTValueType::StringArray(
+            value
+                .as_array()?
+                .iter()
+                .map(|s| s.as_str().map(str::to_string))
+                .collect::<Option<_>>()?,
+        ),
+        _ => return None,
+    })
+}
+
+/// `a` moved `f` of the way to `b`. Anything that isn't a number or same length number array
+/// just holds `a`.
+fn lerp(a: &Value, b: &Value, f: f64) -> Value {
+    match (a, b) {
+        (Value::Number(_), Value::Number(_)) => {
+            let (a, b) = (a.as_f64().unwrap_or(0.0), b.as_f64().unwrap_or(0.0));
+            Value::from(a + (b - a) * f)
+        }
+        (Value::Array(x), Value::Array(y))
+            if x.len() == y.len() && x.iter().chain(y).all(Value::is_number) =>
+        {
+            Value::Array(x.iter().zip(y).map(|(a, b)| lerp(a, b, f)).collect())
+        }
+        _ => a.clone(),
+    }
+}
+
+impl Track {
+    fn type_name(&self) -> &str {
+        if !self.type_name.is_empty() {
+            return &self.type_name;
+        }
+        self.keys.first().map_or("double", |(_, v)| guess_type(v))
+    }
+
+    fn value_at(&self, t: f64) -> Option<NTValueType> {
+        let i = self.keys.iter().rposition(|(time, _)| *time <= t)?;
+        let (t0, v0) = &self.keys[i];
+        let value = match self.keys.get(i + 1) {
+            Some((t1, v1)) if self.smooth => lerp(v0, v1, (t - t0) / (t1 - t0)),
+            _ => v0.clone(),
+        };
+        to_nt(&value, self.type_name())
+    }
+}
+
+impl Scenario {
+    fn load(path: &str) -> Result<Self, String> {
+        let text =
+            fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path, e))?;
+        let scenario: Scenario =
+            serde_json::from_str(&text).map_err(|e| format!("bad scenario {}: {}", path, e))?;
+        if scenario.length <= 0.0 {
+            return Err(format!("{}: length has to be more than 0", path));
+        }
+        for (topic, track) in &scenario.topics {
+            if track.keys.windows(2).any(|k| k[1].0 < k[0].0) {
+                return Err(format!("{}: keys for {} are out of order", path, topic));
+            }
+            if let Some((t, v)) = track
+                .keys
+                .iter()
+                .find(|(_, v)| to_nt(v, track.type_name()).is_none())
+            {
+                return Err(format!(
+                    "{}: {} at {}s isn't a {}",
+                    path,
+                    v,
+                    t,
+                    track.type_name()
+                ));
+            }
+        }
+        Ok(scenario)
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().unwrap_or(DEFAULT_SCENARIO.to_string());
+    let port = args
+        .next()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(DEFAULT_PORT);
+    let scenario = match Scenario::load(&path) {
+        Ok(scenario) => scenario,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let persist = std::env::temp_dir().join("robot-ui-rs-simulator.json");
+    let mut server = NtInstance::start_server(&persist.to_string_lossy(), "", port);
+    println!(
+        "Simulating {} on port {}. Set the dashboard's team number to 0 to connect.",
+        path, port
+    );
+
+    let start = Instant::now();
+    let mut published: HashMap<&str, NTValueType> = HashMap::new();
+    loop {
+        let elapsed = start.elapsed().as_secs_f64();
+        let t = if scenario.once {
+            elapsed.min(scenario.length)
+        } else {
+            elapsed % scenario.length
+        };
+        for (topic, track) in &scenario.topics {
+            let Some(value) = track.value_at(t) else {
+                continue;
+            };
+            if published.get(topic.as_str()) != Some(&value) {
+                server.publish(topic, value.clone());
+                published.insert(topic, value);
+            }
+        }
+
+        // The robot's SendableChooser does this when the dashboard writes `selected`.
+        for table in &scenario.choosers {
+            let active = nt_paths::sendable_key(table, "active");
+            if let Some(selected @ NTValueType::String(_)) =
+                server.get(&nt_paths::sendable_key(table, "selected"))
+                && server.get(&active).as_ref() != Some(&selected)
+            {
+                server.publish(&active, selected);
+            }
+        }
+
+        thread::sleep(TICK);
+    }
+}
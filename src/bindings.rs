@@ -0,0 +1,165 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum Button {
+    A,
+    B,
+    X,
+    Y,
+    #[serde(alias = "LB")]
+    LeftBumper,
+    #[serde(alias = "RB")]
+    RightBumper,
+    #[serde(alias = "LT")]
+    LeftTrigger,
+    #[serde(alias = "RT")]
+    RightTrigger,
+    #[serde(alias = "LS")]
+    LeftStick,
+    #[serde(alias = "RS")]
+    RightStick,
+    Back,
+    Start,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ControllerStyle {
+    #[default]
+    Xbox,
+    #[serde(alias = "play_station")]
+    PlayStation,
+}
+
+impl Button {
+    pub fn label(&self, style: ControllerStyle) -> &'static str {
+        match (self, style) {
+            (Self::A, ControllerStyle::Xbox) => "A",
+            (Self::B, ControllerStyle::Xbox) => "B",
+            (Self::X, ControllerStyle::Xbox) => "X",
+            (Self::Y, ControllerStyle::Xbox) => "Y",
+            (Self::A, ControllerStyle::PlayStation) => "Cross",
+            (Self::B, ControllerStyle::PlayStation) => "Circle",
+            (Self::X, ControllerStyle::PlayStation) => "Square",
+            (Self::Y, ControllerStyle::PlayStation) => "Triangle",
+            (Self::LeftBumper, ControllerStyle::Xbox) => "LB",
+            (Self::RightBumper, ControllerStyle::Xbox) => "RB",
+            (Self::LeftTrigger, ControllerStyle::Xbox) => "LT",
+            (Self::RightTrigger, ControllerStyle::Xbox) => "RT",
+            (Self::LeftBumper, ControllerStyle::PlayStation) => "L1",
+            (Self::RightBumper, ControllerStyle::PlayStation) => "R1",
+            (Self::LeftTrigger, ControllerStyle::PlayStation) => "L2",
+            (Self::RightTrigger, ControllerStyle::PlayStation) => "R2",
+            (Self::LeftStick, ControllerStyle::Xbox) => "LS",
+            (Self::RightStick, ControllerStyle::Xbox) => "RS",
+            (Self::LeftStick, ControllerStyle::PlayStation) => "L3",
+            (Self::RightStick, ControllerStyle::PlayStation) => "R3",
+            (Self::Back, ControllerStyle::Xbox) => "Back",
+            (Self::Start, ControllerStyle::Xbox) => "Start",
+            (Self::Back, ControllerStyle::PlayStation) => "Share",
+            (Self::Start, ControllerStyle::PlayStation) => "Options",
+            (Self::DpadUp, _) => "Up",
+            (Self::DpadDown, _) => "Down",
+            (Self::DpadLeft, _) => "Left",
+            (Self::DpadRight, _) => "Right",
+        }
+    }
+
+    /// Fits on the button in the controller diagram. Only glyphs egui's default fonts have.
+    pub fn short_label(&self, style: ControllerStyle) -> &'static str {
+        match (self, style) {
+            (Self::A, ControllerStyle::PlayStation) => "×",
+            (Self::B, ControllerStyle::PlayStation) => "○",
+            (Self::X, ControllerStyle::PlayStation) => "⬜",
+            (Self::Y, ControllerStyle::PlayStation) => "Δ",
+            (Self::Back, ControllerStyle::Xbox) => "Bk",
+            (Self::Back, ControllerStyle::PlayStation) => "Sh",
+            (Self::Start, _) => "☰",
+            _ => self.label(style),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Binding {
+    pub button: Button,
+    pub action: String,
+}
+
+/// Which buttons do what in each robot state.
+/// Same format whether it comes from the bindings file or from the robot over NT.
+#[derive(Debug, Default, Deserialize)]
+pub struct BindingsConfig {
+    #[serde(default)]
+    pub controller: ControllerStyle,
+    #[serde(default)]
+    pub states: HashMap<String, Vec<Binding>>,
+    /// Used for states that aren't listed.
+    #[serde(default)]
+    pub default: Vec<Binding>,
+}
+
+impl BindingsConfig {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("bad bindings: {}", e))
+    }
+
+    pub fn for_state(&self, state: &str) -> &[Binding] {
+        self.states
+            .get(state)
+            .map(|b| b.as_slice())
+            .unwrap_or(&self.default)
+    }
+}
+
+/// Bindings from the file, overridden by whatever the robot publishes.
+#[derive(Default)]
+pub struct Bindings {
+    pub from_file: BindingsConfig,
+    /// Last JSON the robot published and what it parsed to, so we don't reparse every frame.
+    from_nt: Option<(String, Result<BindingsConfig, String>)>,
+}
+
+impl Bindings {
+    pub fn update_from_nt(&mut self, json: &str) {
+        if json.is_empty() {
+            self.from_nt = None;
+            return;
+        }
+        if self.from_nt.as_ref().is_some_and(|(last, _)| last == json) {
+            return;
+        }
+        let parsed = BindingsConfig::parse(json);
+        if let Err(e) = &parsed {
+            println!("Robot published {}", e);
+        }
+        self.from_nt = Some((json.to_string(), parsed));
+    }
+
+    /// The config to show, and whether it came from the robot.
+    pub fn active(&self) -> (&BindingsConfig, bool) {
+        match &self.from_nt {
+            Some((_, Ok(config))) => (config, true),
+            _ => (&self.from_file, false),
+        }
+    }
+
+    pub fn nt_error(&self) -> Option<&str> {
+        match &self.from_nt {
+            Some((_, Err(e))) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -1,4 +1,22 @@
+pub mod alerts;
 pub mod bottom_panel;
 pub mod central_panel;
+pub mod chooser;
+pub mod field_3d;
+pub mod field_view;
+pub mod fms_panel;
+pub mod game_piece_layer;
+pub mod graphs;
 pub mod input_descriptions;
+pub mod joystick_panel;
 pub mod left_panel;
+pub mod match_timer;
+pub mod mechanism2d;
+#[cfg(feature = "runtime-ntcore")]
+pub mod ntcoreffi_missing;
+pub mod replay_bar;
+pub mod right_panel;
+pub mod sendable_widgets;
+pub mod state_graph;
+pub mod vision_layer;
+pub mod zone_layer;
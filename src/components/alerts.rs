@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use egui::{Align2, Area, Color32, Context, Frame, Id, Label, RichText, Sense, Ui, Vec2};
+
+use crate::{FrcUi, nt_paths, nt_util::NTValueType};
+
+pub const ALERTS_TYPE: &str = "Alerts";
+const TOAST_DURATION_S: f64 = 8.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    pub const ALL: [Severity; 3] = [Severity::Error, Severity::Warning, Severity::Info];
+
+    /// Topic the Alerts group publishes this severity under.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Error => "errors",
+            Self::Warning => "warnings",
+            Self::Info => "infos",
+        }
+    }
+
+    pub fn color(self) -> Color32 {
+        match self {
+            Self::Error => Color32::from_rgb(230, 60, 60),
+            Self::Warning => Color32::from_rgb(255, 180, 0),
+            Self::Info => Color32::from_rgb(80, 160, 255),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlertKey {
+    pub table: String,
+    pub severity: Severity,
+    pub text: String,
+}
+
+pub struct Toast {
+    pub alert: AlertKey,
+    pub shown_at: f64,
+}
+
+/// When each active alert first showed up, plus error toasts we haven't dismissed.
+#[derive(Default)]
+pub struct AlertTracker {
+    pub active: HashMap<AlertKey, f64>,
+    pub toasts: Vec<Toast>,
+}
+
+fn alert_texts<'a>(app: &'a FrcUi, table: &str, severity: Severity) -> &'a [String] {
+    match app
+        .listened_values
+        .get(&nt_paths::sendable_key(table, severity.key()))
+    {
+        Some(NTValueType::StringArray(arr)) => arr,
+        _ => &[],
+    }
+}
+
+/// Call every frame after the Sendables are read.
+pub fn update_alerts(app: &mut FrcUi, now: f64) {
+    let mut current = Vec::new();
+    for sendable in &app.sendables.list {
+        if sendable.type_name != ALERTS_TYPE {
+            continue;
+        }
+        for severity in Severity::ALL {
+            for text in alert_texts(app, &sendable.table, severity) {
+                current.push(AlertKey {
+                    table: sendable.table.clone(),
+                    severity,
+                    text: text.clone(),
+                });
+            }
+        }
+    }
+
+    let tracker = &mut app.alerts;
+    tracker.active.retain(|key, _| current.contains(key));
+    for key in current {
+        if tracker.active.contains_key(&key) {
+            continue;
+        }
+        if key.severity == Severity::Error {
+            tracker.toasts.push(Toast {
+                alert: key.clone(),
+                shown_at: now,
+            });
+        }
+        tracker.active.insert(key, now);
+    }
+    tracker
+        .toasts
+        .retain(|t| now - t.shown_at < TOAST_DURATION_S);
+}
+
+fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.max(0.0) as u64;
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    }
+}
+
+/// Sendable widget for an Alerts group.
+pub fn alerts_widget(ui: &mut Ui, app: &mut FrcUi, table: &str) {
+    let now = ui.input(|i| i.time);
+    let mut alerts: Vec<(&AlertKey, f64)> = app
+        .alerts
+        .active
+        .iter()
+        .filter(|(key, _)| key.table == table)
+        .map(|(key, since)| (key, *since))
+        .collect();
+    if alerts.is_empty() {
+        ui.weak("No active alerts");
+        return;
+    }
+    // Errors first, then newest first.
+    alerts.sort_by(|a, b| a.0.severity.cmp(&b.0.severity).then(b.1.total_cmp(&a.1)));
+    for (key, since) in alerts {
+        ui.horizontal(|ui| {
+            ui.colored_label(key.severity.color(), "●");
+            ui.label(&key.text);
+            ui.weak(format_duration(now - since));
+        });
+    }
+}
+
+/// New errors pop up in the top right corner for a few seconds. Click to dismiss.
+pub fn alert_toasts(ctx: &Context, app: &mut FrcUi) {
+    let mut dismissed = None;
+    for (i, toast) in app.alerts.toasts.iter().enumerate() {
+        Area::new(Id::new(("AlertToast", i)))
+            .anchor(Align2::RIGHT_TOP, Vec2::new(-10.0, 60.0 + i as f32 * 50.0))
+            .interactable(true)
+            .show(ctx, |ui| {
+                Frame::popup(ui.style())
+                    .fill(Severity::Error.color().gamma_multiply(0.9))
+                    .show(ui, |ui| {
+                        let name = toast.alert.table.rsplit('/').next().unwrap_or_default();
+                        let text = RichText::new(format!("{}: {}", name, toast.alert.text))
+                            .color(Color32::WHITE);
+                        let response = ui.add(Label::new(text).sense(Sense::click()));
+                        if response.clicked() {
+                            dismissed = Some(i);
+                        }
+                    });
+            });
+    }
+    if let Some(i) = dismissed {
+        app.alerts.toasts.remove(i);
+    }
+    if !app.alerts.toasts.is_empty() {
+        // Keep repainting so toasts go away on time.
+        ctx.request_repaint();
+    }
+}
@@ -1,9 +1,9 @@
-use egui::{ComboBox, Layout, TopBottomPanel};
-use ntcore_sys::NT_SetString;
+use egui::{Color32, Layout, TopBottomPanel};
 
 use crate::{
-    FrcUi, nt_paths,
-    nt_util::{NTValueType, get_entry_handle, to_wpi_string},
+    FrcUi,
+    components::{chooser::chooser, replay_bar::replay_menu},
+    nt_paths,
 };
 
 pub fn bottom_panel(ctx: &egui::Context, app: &mut FrcUi) {
@@ -13,36 +13,15 @@ pub fn bottom_panel(ctx: &egui::Context, app: &mut FrcUi) {
                 if ui.button("Connection Settings").clicked() {
                     app.settings_modal_open = true;
                 }
-                let auto_chooser_box = ComboBox::new("AutoChooserBox", "Currently selected auto:");
-                let mut selected = {
-                    let val = app.listened_values.get(nt_paths::AUTO_CHOOSER_ACTIVE);
-                    if let Some(NTValueType::String(s)) = val {
-                        s.to_owned()
-                    } else {
-                        String::from("None")
-                    }
-                };
-                auto_chooser_box
-                    .selected_text(selected.clone())
-                    .show_ui(ui, |inner_ui| {
-                        if let Some(NTValueType::StringArray(arr)) =
-                            app.listened_values.get(nt_paths::AUTO_CHOOSER_OPTIONS)
-                        {
-                            for option in arr {
-                                inner_ui.selectable_value(&mut selected, option.to_owned(), option);
-                            }
-                        }
-                    });
-
-                if selected != "None" {
-                    unsafe {
-                        NT_SetString(
-                            get_entry_handle(nt_paths::AUTO_CHOOSER_ACTIVE, app.nt),
-                            0,
-                            &to_wpi_string(&selected),
-                        )
-                    };
+                if ui.button("Graphs").clicked() {
+                    app.graphs.open = !app.graphs.open;
+                }
+                replay_menu(ui, app);
+                if let Some(file) = app.logger.current_file() {
+                    let name = file.file_name().unwrap_or_default().to_string_lossy();
+                    ui.colored_label(Color32::RED, format!("● REC {}", name));
                 }
+                chooser(ui, app, nt_paths::AUTO_CHOOSER, "Currently selected auto:");
             });
         });
     });
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use crate::{
+    FrcUi,
+    data_source::DataSource,
+    nt_paths,
+    nt_util::{ListenedValues, NTValueType},
+};
+use egui::{Color32, ComboBox, Ui};
+
+pub const CHOOSER_TYPE: &str = "String Chooser";
+/// How long the robot gets to echo our pick back in `active` before we complain.
+const ACTIVE_TIMEOUT_S: f64 = 1.0;
+
+/// What we last wrote to each chooser's `selected` topic, and when (egui time).
+#[derive(Default)]
+pub struct Choosers {
+    written: HashMap<String, (String, f64)>,
+}
+
+/// Reads a SendableChooser's topics into the listened values.
+pub fn poll_chooser(values: &mut ListenedValues, table: &str, source: &dyn DataSource) {
+    for key in [".type", "default", "selected", "active"] {
+        let path = nt_paths::sendable_key(table, key);
+        let value = source.get_string(&path, "");
+        values.insert(path, NTValueType::String(value));
+    }
+    let path = nt_paths::sendable_key(table, "options");
+    let options = source.get_string_array(&path);
+    values.insert(path, NTValueType::StringArray(options));
+}
+
+fn string<'a>(values: &'a ListenedValues, table: &str, key: &str) -> &'a str {
+    match values.get(&nt_paths::sendable_key(table, key)) {
+        Some(NTValueType::String(s)) => s,
+        _ => "",
+    }
+}
+
+/// Dropdown for a WPILib SendableChooser under `table`.
+/// Only writes `selected` when the user actually picks something.
+pub fn chooser(ui: &mut Ui, app: &mut FrcUi, table: &str, label: &str) {
+    let values = &app.listened_values;
+    if string(values, table, ".type") != CHOOSER_TYPE {
+        ui.weak(format!("{} no chooser on the robot", label));
+        return;
+    }
+    let default = string(values, table, "default");
+    // Owned since we write to the source further down.
+    let active = string(values, table, "active").to_string();
+    let options = match values.get(&nt_paths::sendable_key(table, "options")) {
+        Some(NTValueType::StringArray(arr)) => arr.as_slice(),
+        _ => &[],
+    };
+
+    let shown = if active.is_empty() {
+        default.to_string()
+    } else {
+        active.clone()
+    };
+    let mut picked = shown.clone();
+    ComboBox::new(table, label)
+        .selected_text(if shown.is_empty() { "None" } else { &shown })
+        .show_ui(ui, |ui| {
+            for option in options {
+                let text = if option == default {
+                    format!("{} (default)", option)
+                } else {
+                    option.clone()
+                };
+                ui.selectable_value(&mut picked, option.clone(), text);
+            }
+        });
+
+    let now = ui.input(|i| i.time);
+    if picked != shown {
+        app.data_mut().publish(
+            &nt_paths::sendable_key(table, "selected"),
+            NTValueType::String(picked.clone()),
+        );
+        app.choosers
+            .written
+            .insert(table.to_string(), (picked, now));
+    }
+
+    if let Some((written, at)) = app.choosers.written.get(table)
+        && *written != active
+        && now - at > ACTIVE_TIMEOUT_S
+    {
+        ui.colored_label(
+            Color32::from_rgb(255, 120, 0),
+            format!("Picked {} but robot is running {}", written, shown),
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use std::ops::{Add, Mul, Sub};
+
+use egui::{
+    Color32, FontId, Image, Mesh, Painter, Pos2, Rect, Shape, Stroke, Ui, Vec2, epaint::Vertex,
+};
+
+use crate::{
+    FrcUi,
+    components::{
+        field_view::{FIELD_LENGTH_M, FIELD_WIDTH_M, robot_pose},
+        game_piece_layer::parse_game_pieces,
+    },
+    nt_paths,
+    nt_util::NTValueType,
+};
+
+const ROBOT_SIZE_M: f32 = 0.8;
+const ROBOT_HEIGHT_M: f32 = 0.5;
+const WALL_HEIGHT_M: f32 = 0.5;
+const FOV_DEG: f32 = 60.0;
+const NEAR_M: f32 = 0.1;
+// The floor is split up so egui's affine texture mapping doesn't warp the image too badly.
+const FLOOR_SUBDIVISIONS: (usize, usize) = (16, 8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraPreset {
+    DriverStation,
+    TopDown,
+    FollowCam,
+}
+
+impl CameraPreset {
+    pub const ALL: [CameraPreset; 3] = [Self::DriverStation, Self::TopDown, Self::FollowCam];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DriverStation => "Driver station",
+            Self::TopDown => "Top-down",
+            Self::FollowCam => "Follow robot",
+        }
+    }
+}
+
+pub struct Field3dView {
+    pub enabled: bool,
+    pub preset: CameraPreset,
+}
+
+impl Default for Field3dView {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            preset: CameraPreset::DriverStation,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Vec3 {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+fn v3(x: f32, y: f32, z: f32) -> Vec3 {
+    Vec3 { x, y, z }
+}
+
+impl Vec3 {
+    fn dot(self, o: Vec3) -> f32 {
+        self.x * o.x + self.y * o.y + self.z * o.z
+    }
+
+    fn cross(self, o: Vec3) -> Vec3 {
+        v3(
+            self.y * o.z - self.z * o.y,
+            self.z * o.x - self.x * o.z,
+            self.x * o.y - self.y * o.x,
+        )
+    }
+
+    fn normalized(self) -> Vec3 {
+        self * (1.0 / self.dot(self).sqrt())
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+    fn add(self, o: Vec3) -> Vec3 {
+        v3(self.x + o.x, self.y + o.y, self.z + o.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+    fn sub(self, o: Vec3) -> Vec3 {
+        v3(self.x - o.x, self.y - o.y, self.z - o.z)
+    }
+}
+
+impl Mul<f32> for Vec3 {
+    type Output = Vec3;
+    fn mul(self, s: f32) -> Vec3 {
+        v3(self.x * s, self.y * s, self.z * s)
+    }
+}
+
+/// Simple pinhole camera. Everything is projected on the CPU and drawn with egui's painter,
+/// so this works the same with or without a GPU.
+struct Camera {
+    eye: Vec3,
+    forward: Vec3,
+    right: Vec3,
+    up: Vec3,
+    focal: f32,
+    center: Pos2,
+}
+
+impl Camera {
+    fn look_at(eye: Vec3, target: Vec3, world_up: Vec3, viewport: Rect) -> Self {
+        let forward = (target - eye).normalized();
+        let right = forward.cross(world_up).normalized();
+        let up = right.cross(forward);
+        Self {
+            eye,
+            forward,
+            right,
+            up,
+            focal: viewport.height() / 2.0 / (FOV_DEG.to_radians() / 2.0).tan(),
+            center: viewport.center(),
+        }
+    }
+
+    /// Screen position and depth, or None if the point is behind the camera.
+    fn project(&self, p: Vec3) -> Option<(Pos2, f32)> {
+        let d = p - self.eye;
+        let depth = d.dot(self.forward);
+        if depth < NEAR_M {
+            return None;
+        }
+        Some((
+            self.center + Vec2::new(d.dot(self.right), -d.dot(self.up)) * (self.focal / depth),
+            depth,
+        ))
+    }
+
+    fn line(&self, painter: &Painter, a: Vec3, b: Vec3, stroke: Stroke) {
+        // Clip against the near plane so lines going behind the camera don't flip around.
+        let (da, db) = (
+            (a - self.eye).dot(self.forward),
+            (b - self.eye).dot(self.forward),
+        );
+        if da < NEAR_M && db < NEAR_M {
+            return;
+        }
+        let clip = |inside: Vec3, outside: Vec3, di: f32, dout: f32| {
+            inside + (outside - inside) * ((di - NEAR_M) / (di - dout))
+        };
+        let (a, b) = if da < NEAR_M {
+            (clip(b, a, db, da), b)
+        } else if db < NEAR_M {
+            (a, clip(a, b, da, db))
+        } else {
+            (a, b)
+        };
+        if let (Some((pa, _)), Some((pb, _))) = (self.project(a), self.project(b)) {
+            painter.line_segment([pa, pb], stroke);
+        }
+    }
+}
+
+fn camera_for(app: &FrcUi, viewport: Rect) -> Camera {
+    let (l, w) = (FIELD_LENGTH_M as f32, FIELD_WIDTH_M as f32);
+    let is_red = matches!(
+        app.listened_values.get(nt_paths::FMS_IS_RED_ALLIANCE),
+        Some(NTValueType::Boolean(true))
+    );
+    let z_up = v3(0.0, 0.0, 1.0);
+    let field_center = v3(l / 2.0, w / 2.0, 0.0);
+    let robot = robot_pose(app);
+
+    match (app.field_3d.preset, robot) {
+        (CameraPreset::FollowCam, Some((x, y, heading))) => {
+            let pos = v3(x as f32, y as f32, 0.0);
+            let dir = v3(heading.cos() as f32, heading.sin() as f32, 0.0);
+            Camera::look_at(
+                pos - dir * 2.5 + v3(0.0, 0.0, 1.8),
+                pos + dir * 1.5,
+                z_up,
+                viewport,
+            )
+        }
+        (CameraPreset::TopDown, _) => {
+            // Same orientation as the 2D view, flipped around for red.
+            let up = if is_red {
+                v3(0.0, -1.0, 0.0)
+            } else {
+                v3(0.0, 1.0, 0.0)
+            };
+            let half_fov_tan = (FOV_DEG.to_radians() / 2.0).tan();
+            let aspect = viewport.width() / viewport.height();
+            let height = (w / 2.0).max(l / 2.0 / aspect) / half_fov_tan * 1.05;
+            Camera::look_at(
+                field_center + v3(0.0, 0.0, height),
+                field_center,
+                up,
+                viewport,
+            )
+        }
+        // Follow cam without a robot pose falls back to the driver station view.
+        _ => {
+            let eye = if is_red {
+                v3(l + 2.0, w / 2.0, 3.0)
+            } else {
+                v3(-2.0, w / 2.0, 3.0)
+            };
+            Camera::look_at(eye, field_center, z_up, viewport)
+        }
+    }
+}
+
+pub fn field_3d_view(ui: &Ui, app: &FrcUi, viewport: Rect) {
+    let painter = ui.painter_at(viewport);
+    painter.rect_filled(viewport, 0.0, Color32::from_rgb(20, 20, 30));
+    let cam = camera_for(app, viewport);
+    let (l, w) = (FIELD_LENGTH_M as f32, FIELD_WIDTH_M as f32);
+
+    draw_floor(ui, &painter, &cam);
+
+    // Walls
+    let wall = Stroke::new(2.0, Color32::from_gray(180));
+    let corners = [
+        v3(0.0, 0.0, 0.0),
+        v3(l, 0.0, 0.0),
+        v3(l, w, 0.0),
+        v3(0.0, w, 0.0),
+    ];
+    let lift = v3(0.0, 0.0, WALL_HEIGHT_M);
+    for i in 0..4 {
+        let (a, b) = (corners[i], corners[(i + 1) % 4]);
+        cam.line(&painter, a, b, wall);
+        cam.line(&painter, a + lift, b + lift, wall);
+        cam.line(&painter, a, a + lift, wall);
+    }
+
+    // AprilTags
+    if let Some(layout) = &app.apriltag_layout {
+        for tag in &layout.tags {
+            let t = &tag.pose.translation;
+            let center = v3(t.x as f32, t.y as f32, t.z as f32);
+            let yaw = tag.pose.rotation.quaternion.yaw() as f32;
+            // Tags are ~16.5cm, drawn as a vertical square facing along their yaw.
+            let side = v3(-yaw.sin(), yaw.cos(), 0.0) * 0.083;
+            let vert = v3(0.0, 0.0, 0.083);
+            let quad = [
+                center - side - vert,
+                center + side - vert,
+                center + side + vert,
+                center - side + vert,
+            ];
+            let projected: Option<Vec<Pos2>> = quad
+                .iter()
+                .map(|p| cam.project(*p).map(|(pos, _)| pos))
+                .collect();
+            if let Some(points) = projected {
+                painter.add(Shape::convex_polygon(
+                    points,
+                    Color32::from_gray(230),
+                    Stroke::new(1.0, Color32::BLACK),
+                ));
+            }
+            if let Some((pos, depth)) = cam.project(center + vert * 2.0) {
+                painter.text(
+                    pos,
+                    egui::Align2::CENTER_BOTTOM,
+                    tag.id.to_string(),
+                    FontId::proportional((120.0 / depth).clamp(8.0, 14.0)),
+                    Color32::WHITE,
+                );
+            }
+        }
+    }
+
+    // Game pieces
+    if let Some(NTValueType::DoubleArray(positions)) =
+        app.listened_values.get(nt_paths::KNOWN_LUNITE_POSITIONS)
+    {
+        let (pieces, _) = parse_game_pieces(positions, &[]);
+        for piece in pieces {
+            if let Some((pos, depth)) = cam.project(v3(piece.x as f32, piece.y as f32, 0.1)) {
+                painter.circle_filled(pos, 0.1 * cam.focal / depth, Color32::from_rgb(0, 255, 0));
+            }
+        }
+    }
+
+    if let Some((x, y, heading)) = robot_pose(app) {
+        draw_robot(&painter, &cam, x as f32, y as f32, heading as f32);
+    }
+}
+
+fn draw_floor(ui: &Ui, painter: &Painter, cam: &Camera) {
+    let (l, w) = (FIELD_LENGTH_M as f32, FIELD_WIDTH_M as f32);
+    let texture = Image::new("bytes://bbots25-field.png")
+        .load_for_size(ui.ctx(), Vec2::new(1148.0, 574.0))
+        .ok()
+        .and_then(|poll| poll.texture_id());
+
+    let mut mesh = match texture {
+        Some(id) => Mesh::with_texture(id),
+        None => Mesh::default(),
+    };
+    let color = if texture.is_some() {
+        Color32::WHITE
+    } else {
+        Color32::from_rgb(40, 90, 40)
+    };
+    let (nx, ny) = FLOOR_SUBDIVISIONS;
+    let mut idx = vec![None; (nx + 1) * (ny + 1)];
+    for j in 0..=ny {
+        for i in 0..=nx {
+            let (u, v) = (i as f32 / nx as f32, j as f32 / ny as f32);
+            // Image v goes down, field y goes up.
+            if let Some((pos, _)) = cam.project(v3(u * l, (1.0 - v) * w, 0.0)) {
+                idx[j * (nx + 1) + i] = Some(mesh.vertices.len() as u32);
+                mesh.vertices.push(Vertex {
+                    pos,
+                    uv: Pos2::new(u, v),
+                    color,
+                });
+            }
+        }
+    }
+    for j in 0..ny {
+        for i in 0..nx {
+            let corner = |di: usize, dj: usize| idx[(j + dj) * (nx + 1) + i + di];
+            if let (Some(a), Some(b), Some(c), Some(d)) =
+                (corner(0, 0), corner(1, 0), corner(1, 1), corner(0, 1))
+            {
+                mesh.add_triangle(a, b, c);
+                mesh.add_triangle(a, c, d);
+            }
+        }
+    }
+    painter.add(Shape::mesh(mesh));
+}
+
+fn draw_robot(painter: &Painter, cam: &Camera, x: f32, y: f32, heading: f32) {
+    let pos = v3(x, y, 0.0);
+    let fwd = v3(heading.cos(), heading.sin(), 0.0) * (ROBOT_SIZE_M / 2.0);
+    let left = v3(-heading.sin(), heading.cos(), 0.0) * (ROBOT_SIZE_M / 2.0);
+    let up = v3(0.0, 0.0, ROBOT_HEIGHT_M);
+    let base = [
+        pos + fwd + left,
+        pos - fwd + left,
+        pos - fwd - left,
+        pos + fwd - left,
+    ];
+    let top = base.map(|p| p + up);
+
+    let mut faces: Vec<([Vec3; 4], Color32)> = (0..4)
+        .map(|i| {
+            let j = (i + 1) % 4;
+            // Front face (between corners 3 and 0) is red so you can tell which way it's facing.
+            let color = if i == 3 {
+                Color32::from_rgb(200, 30, 30)
+            } else {
+                Color32::from_rgb(30, 30, 150)
+            };
+            ([base[i], base[j], top[j], top[i]], color)
+        })
+        .collect();
+    faces.push((top, Color32::from_rgb(60, 60, 190)));
+
+    // Painter's algorithm: draw far faces first.
+    let center_depth = |quad: &[Vec3; 4]| {
+        quad.iter()
+            .map(|p| (*p - cam.eye).dot(cam.forward))
+            .sum::<f32>()
+    };
+    faces.sort_by(|a, b| center_depth(&b.0).total_cmp(&center_depth(&a.0)));
+    for (quad, color) in faces {
+        let projected: Option<Vec<Pos2>> = quad
+            .iter()
+            .map(|p| cam.project(*p).map(|(pos, _)| pos))
+            .collect();
+        if let Some(points) = projected {
+            painter.add(Shape::convex_polygon(
+                points,
+                color,
+                Stroke::new(1.0, Color32::BLACK),
+            ));
+        }
+    }
+}
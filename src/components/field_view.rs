@@ -0,0 +1,287 @@
+use egui::{Color32, ComboBox, DragValue, Image, Key, Pos2, Rect, Sense, Ui, UiBuilder, Vec2};
+
+use crate::{
+    FrcUi,
+    components::{
+        field_3d::{CameraPreset, field_3d_view},
+        game_piece_layer::draw_game_pieces,
+        vision_layer::draw_vision,
+        zone_layer::{draw_zones, robot_zone_color},
+    },
+    nt_paths,
+    nt_util::NTValueType,
+};
+
+pub const FIELD_LENGTH_M: f64 = 16.4592;
+pub const FIELD_WIDTH_M: f64 = 8.2296;
+
+const MIN_ZOOM: f32 = 1.0;
+const MAX_ZOOM: f32 = 8.0;
+// Room left under the field for the game time, state, bindings etc.
+const RESERVED_HEIGHT: f32 = 200.0;
+const KEYBOARD_PAN_SPEED: f32 = 15.0;
+
+/// Zoom/pan state of the field canvas. Lives in `FrcUi` so it survives between frames.
+pub struct FieldView {
+    pub zoom: f32,
+    /// Offset of the field's center from the canvas center, in screen points.
+    pub pan: Vec2,
+    pub follow_robot: bool,
+    /// Rotate the field 180° when we're on red so it's always drawn from our driver station.
+    pub alliance_perspective: bool,
+    pub show_vision: bool,
+}
+
+impl Default for FieldView {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            pan: Vec2::ZERO,
+            follow_robot: false,
+            alliance_perspective: true,
+            show_vision: true,
+        }
+    }
+}
+
+impl FieldView {
+    pub fn reset(&mut self) {
+        self.zoom = 1.0;
+        self.pan = Vec2::ZERO;
+        self.follow_robot = false;
+    }
+
+    /// Zooms while keeping whatever is under `anchor` in place.
+    fn zoom_around(&mut self, canvas: Rect, anchor: Pos2, factor: f32) {
+        let new_zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        let real_factor = new_zoom / self.zoom;
+        let field_center = canvas.center() + self.pan;
+        let new_center = anchor + (field_center - anchor) * real_factor;
+        self.pan = new_center - canvas.center();
+        self.zoom = new_zoom;
+    }
+
+    /// Keeps the canvas covered by the field so we never pan off into nothing.
+    fn clamp_pan(&mut self, canvas: Rect) {
+        let max = canvas.size() * (self.zoom - 1.0).max(0.0) / 2.0;
+        self.pan = self.pan.clamp(-max, max);
+    }
+}
+
+/// Maps field coordinates (meters, blue alliance origin, WPILib convention) to screen points.
+#[derive(Clone, Copy)]
+pub struct FieldTransform {
+    /// Where the whole field ends up on screen. Can be much larger than the canvas when zoomed.
+    pub rect: Rect,
+    /// True when the field is rotated 180° (red alliance perspective).
+    pub flipped: bool,
+}
+
+impl FieldTransform {
+    pub fn to_screen(self, x: f64, y: f64) -> Pos2 {
+        let mut nx = (x / FIELD_LENGTH_M) as f32;
+        // Screen y points down, field y points up.
+        let mut ny = 1.0 - (y / FIELD_WIDTH_M) as f32;
+        if self.flipped {
+            nx = 1.0 - nx;
+            ny = 1.0 - ny;
+        }
+        Pos2::new(
+            self.rect.min.x + nx * self.rect.width(),
+            self.rect.min.y + ny * self.rect.height(),
+        )
+    }
+
+    /// Converts a field heading (radians, CCW positive) into a screen direction.
+    pub fn heading_to_screen(self, heading: f64) -> Vec2 {
+        let dir = Vec2::new(heading.cos() as f32, -heading.sin() as f32);
+        if self.flipped { -dir } else { dir }
+    }
+
+    /// How many screen points one field meter takes up.
+    pub fn points_per_meter(self) -> f32 {
+        self.rect.width() / FIELD_LENGTH_M as f32
+    }
+}
+
+/// Returns (x, y, heading in radians) of the robot, if we know it.
+pub fn robot_pose(app: &FrcUi) -> Option<(f64, f64, f64)> {
+    if let Some(NTValueType::DoubleArray(arr)) =
+        app.listened_values.get(nt_paths::ROBOT_2D_POSITION)
+    {
+        if arr.len() >= 3 {
+            Some((arr[0], arr[1], arr[2].to_radians()))
+        } else {
+            None // Should never happen.
+        }
+    } else {
+        None
+    }
+}
+
+pub fn field_view(ui: &mut Ui, app: &mut FrcUi) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut app.field_3d.enabled, "3D");
+        if app.field_3d.enabled {
+            ComboBox::from_id_salt("Field3dCamera")
+                .selected_text(app.field_3d.preset.name())
+                .show_ui(ui, |ui| {
+                    for preset in CameraPreset::ALL {
+                        ui.selectable_value(&mut app.field_3d.preset, preset, preset.name());
+                    }
+                });
+            return;
+        }
+        ui.checkbox(&mut app.field_view.follow_robot, "Follow robot (F)");
+        ui.checkbox(
+            &mut app.field_view.alliance_perspective,
+            "Alliance perspective",
+        );
+        if ui.button("Reset view (0)").clicked() {
+            app.field_view.reset();
+        }
+        ui.weak(format!("{:.1}x", app.field_view.zoom));
+        ui.separator();
+        ui.checkbox(&mut app.zone_layer.show, "Zones");
+        ui.checkbox(&mut app.field_view.show_vision, "Vision");
+        ui.checkbox(&mut app.game_piece_layer.show_labels, "Lunite labels");
+        ui.label("Fade after:");
+        ui.add(
+            DragValue::new(&mut app.game_piece_layer.max_age_s)
+                .range(0.5..=60.0)
+                .speed(0.1)
+                .suffix(" s"),
+        );
+    });
+
+    // Fit a 2:1 canvas into whatever space is left.
+    let available = ui.available_size() - Vec2::new(0.0, RESERVED_HEIGHT);
+    let width = available.x.min(available.y.max(100.0) * 2.0);
+    let (canvas, response) =
+        ui.allocate_exact_size(Vec2::new(width, width / 2.0), Sense::click_and_drag());
+
+    if app.field_3d.enabled {
+        field_3d_view(ui, app, canvas);
+        return;
+    }
+
+    handle_input(ui, app, canvas, &response);
+
+    let is_red = matches!(
+        app.listened_values.get(nt_paths::FMS_IS_RED_ALLIANCE),
+        Some(NTValueType::Boolean(true))
+    );
+    let flipped = app.field_view.alliance_perspective && is_red;
+
+    let mut field_rect =
+        Rect::from_center_size(canvas.center(), canvas.size() * app.field_view.zoom);
+    let robot = robot_pose(app);
+    if app.field_view.follow_robot
+        && let Some((x, y, _)) = robot
+    {
+        let robot_on_screen = FieldTransform {
+            rect: field_rect,
+            flipped,
+        }
+        .to_screen(x, y);
+        app.field_view.pan = canvas.center() - robot_on_screen;
+    }
+    app.field_view.clamp_pan(canvas);
+    field_rect = field_rect.translate(app.field_view.pan);
+    let transform = FieldTransform {
+        rect: field_rect,
+        flipped,
+    };
+
+    let mut canvas_ui = ui.new_child(UiBuilder::new().max_rect(canvas));
+    canvas_ui.set_clip_rect(canvas.intersect(ui.clip_rect()));
+    canvas_ui
+        .painter()
+        .rect_filled(canvas, 0.0, ui.visuals().extreme_bg_color);
+
+    let mut img = Image::new("bytes://bbots25-field.png");
+    if flipped {
+        // Swapping the uv corners draws the image rotated by 180°.
+        img = img.uv(Rect::from_min_max(Pos2::new(1.0, 1.0), Pos2::new(0.0, 0.0)));
+    }
+    img.paint_at(&canvas_ui, field_rect);
+
+    let painter = canvas_ui.painter();
+    if app.zone_layer.show {
+        draw_zones(painter, &transform, app);
+    }
+    if app.field_view.show_vision {
+        draw_vision(painter, &transform, app);
+    }
+    if let Some((x, y, _)) = robot {
+        let center_pos = transform.to_screen(x, y);
+        let marker_color = robot_zone_color(app).unwrap_or(Color32::from_rgb(30, 30, 150));
+        painter.circle_filled(center_pos, 15.0, marker_color);
+        painter.circle_filled(center_pos, 5.0, Color32::from_rgb(255, 0, 0));
+    }
+
+    draw_game_pieces(painter, &transform, app);
+}
+fn handle_input(ui: &Ui, app: &mut FrcUi, canvas: Rect, response: &egui::Response) {
+    let view = &mut app.field_view;
+
+    if response.dragged() {
+        view.pan += response.drag_delta();
+        view.follow_robot = false;
+    }
+    if response.double_clicked() {
+        view.reset();
+    }
+
+    if response.hovered() {
+        let (zoom_delta, scroll, hover_pos) = ui.input(|i| {
+            (
+                i.zoom_delta(),
+                i.smooth_scroll_delta.y,
+                i.pointer.hover_pos(),
+            )
+        });
+        let anchor = hover_pos.unwrap_or(canvas.center());
+        // Plain scroll zooms too, there's nothing to scroll inside the field.
+        let factor = zoom_delta * (scroll / 200.0).exp();
+        if factor != 1.0 {
+            view.zoom_around(canvas, anchor, factor);
+        }
+    }
+
+    // Don't steal keys from text fields (settings modal etc.)
+    if ui.ctx().wants_keyboard_input() {
+        return;
+    }
+    ui.input(|i| {
+        if i.key_pressed(Key::Plus) || i.key_pressed(Key::Equals) {
+            view.zoom_around(canvas, canvas.center(), 1.25);
+        }
+        if i.key_pressed(Key::Minus) {
+            view.zoom_around(canvas, canvas.center(), 0.8);
+        }
+        if i.key_pressed(Key::Num0) {
+            view.reset();
+        }
+        if i.key_pressed(Key::F) {
+            view.follow_robot = !view.follow_robot;
+        }
+        let mut pan = Vec2::ZERO;
+        if i.key_down(Key::ArrowLeft) {
+            pan.x += KEYBOARD_PAN_SPEED;
+        }
+        if i.key_down(Key::ArrowRight) {
+            pan.x -= KEYBOARD_PAN_SPEED;
+        }
+        if i.key_down(Key::ArrowUp) {
+            pan.y += KEYBOARD_PAN_SPEED;
+        }
+        if i.key_down(Key::ArrowDown) {
+            pan.y -= KEYBOARD_PAN_SPEED;
+        }
+        if pan != Vec2::ZERO {
+            view.pan += pan;
+            view.follow_robot = false;
+        }
+    });
+}
@@ -0,0 +1,77 @@
+use egui::{Color32, CornerRadius, Frame, RichText, TopBottomPanel, Ui};
+
+use crate::{
+    FrcUi,
+    fms::{ControlWord, fms_info},
+};
+
+fn badge(ui: &mut Ui, text: &str, fill: Color32) {
+    Frame::new()
+        .fill(fill)
+        .corner_radius(CornerRadius::same(4))
+        .inner_margin(egui::Margin::symmetric(6, 2))
+        .show(ui, |ui| {
+            ui.label(RichText::new(text).color(Color32::WHITE).strong());
+        });
+}
+
+/// Shows a control word bit, lit up when set.
+fn flag(ui: &mut Ui, name: &str, set: bool, on_color: Color32) {
+    let fill = if set {
+        on_color
+    } else {
+        Color32::from_gray(60)
+    };
+    badge(ui, name, fill);
+}
+
+pub fn fms_panel(ctx: &egui::Context, app: &mut FrcUi) {
+    let info = fms_info(app);
+    TopBottomPanel::top("FmsPanel").show(ctx, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            if info.on_field() {
+                badge(ui, "FMS", Color32::from_rgb(0, 140, 60));
+            } else {
+                badge(ui, "NO FMS / PRACTICE", Color32::from_rgb(200, 120, 0));
+            }
+
+            if !info.event_name.is_empty() {
+                ui.label(RichText::new(&info.event_name).strong());
+            }
+            let match_label = info.match_label();
+            if !match_label.is_empty() {
+                ui.label(format!("{} - {}", info.match_type_name(), match_label));
+            }
+            if info.station_number > 0 {
+                let (alliance, color) = if info.is_red {
+                    ("Red", Color32::from_rgb(255, 50, 50))
+                } else {
+                    ("Blue", Color32::from_rgb(50, 50, 255))
+                };
+                ui.label(
+                    RichText::new(format!("{} {}", alliance, info.station_number)).color(color),
+                );
+            }
+            if !info.game_specific_message.is_empty() {
+                ui.label(format!("Game data: {}", info.game_specific_message));
+            }
+
+            ui.separator();
+            match info.control_word {
+                Some(word) => control_word_flags(ui, word),
+                None => {
+                    ui.weak("No control word yet");
+                }
+            }
+        });
+    });
+}
+
+fn control_word_flags(ui: &mut Ui, word: ControlWord) {
+    flag(ui, "Enabled", word.enabled, Color32::from_rgb(0, 150, 0));
+    flag(ui, "Auto", word.autonomous, Color32::from_rgb(40, 110, 200));
+    flag(ui, "Test", word.test, Color32::from_rgb(140, 70, 200));
+    flag(ui, "E-Stop", word.e_stop, Color32::from_rgb(200, 0, 0));
+    flag(ui, "FMS", word.fms_attached, Color32::from_rgb(0, 140, 60));
+    flag(ui, "DS", word.ds_attached, Color32::from_rgb(0, 140, 60));
+}
@@ -0,0 +1,168 @@
+use std::collections::HashSet;
+
+use egui::{Align2, Color32, FontId, Painter, Stroke};
+
+use crate::{
+    FrcUi,
+    components::field_view::{FieldTransform, robot_pose},
+    nt_paths,
+    nt_util::NTValueType,
+};
+
+// Values per game piece in KNOWN_LUNITE_POSITIONS and KNOWN_LUNITE_METADATA.
+const POSITION_STRIDE: usize = 2;
+const METADATA_STRIDE: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamePiece {
+    pub x: f64,
+    pub y: f64,
+    /// 0.0 - 1.0
+    pub confidence: Option<f64>,
+    /// Seconds since the piece was last seen.
+    pub age: Option<f64>,
+    pub id: Option<i64>,
+}
+
+/// Settings for the game piece layer, plus bookkeeping so we don't spam the log every frame.
+pub struct GamePieceLayer {
+    pub show_labels: bool,
+    /// Pieces older than this are drawn almost transparent.
+    pub max_age_s: f64,
+    warned_lengths: HashSet<(usize, usize)>,
+}
+
+impl Default for GamePieceLayer {
+    fn default() -> Self {
+        Self {
+            show_labels: true,
+            max_age_s: 5.0,
+            warned_lengths: HashSet::new(),
+        }
+    }
+}
+
+/// Turns the flat `[x, y, x, y...]` position array (and optional flat
+/// `[confidence, age, id, ...]` metadata array) into pieces.
+/// Returns a description of the problem along with the pieces if either array is malformed;
+/// whatever could be parsed is still returned.
+pub fn parse_game_pieces(positions: &[f64], metadata: &[f64]) -> (Vec<GamePiece>, Option<String>) {
+    let mut problems = Vec::new();
+    if !positions.len().is_multiple_of(POSITION_STRIDE) {
+        problems.push(format!(
+            "lunite position array has odd length {}, ignoring trailing value",
+            positions.len()
+        ));
+    }
+    let count = positions.len() / POSITION_STRIDE;
+    if !metadata.is_empty() && metadata.len() != count * METADATA_STRIDE {
+        problems.push(format!(
+            "lunite metadata array has length {}, expected {} ({} pieces * {})",
+            metadata.len(),
+            count * METADATA_STRIDE,
+            count,
+            METADATA_STRIDE
+        ));
+    }
+
+    let pieces = positions
+        .chunks_exact(POSITION_STRIDE)
+        .enumerate()
+        .map(|(i, pos)| {
+            let meta = metadata
+                .get(i * METADATA_STRIDE..(i + 1) * METADATA_STRIDE)
+                .filter(|m| m.len() == METADATA_STRIDE);
+            GamePiece {
+                x: pos[0],
+                y: pos[1],
+                confidence: meta.map(|m| m[0]),
+                age: meta.map(|m| m[1]),
+                id: meta.map(|m| m[2] as i64),
+            }
+        })
+        .collect();
+
+    let problem = if problems.is_empty() {
+        None
+    } else {
+        Some(problems.join("; "))
+    };
+    (pieces, problem)
+}
+
+pub fn draw_game_pieces(painter: &Painter, transform: &FieldTransform, app: &mut FrcUi) {
+    let positions = match app.listened_values.get(nt_paths::KNOWN_LUNITE_POSITIONS) {
+        Some(NTValueType::DoubleArray(arr)) => arr.as_slice(),
+        _ => &[],
+    };
+    let metadata = match app.listened_values.get(nt_paths::KNOWN_LUNITE_METADATA) {
+        Some(NTValueType::DoubleArray(arr)) => arr.as_slice(),
+        _ => &[],
+    };
+    let (pieces, problem) = parse_game_pieces(positions, metadata);
+    if let Some(problem) = problem {
+        // Only complain once per bad shape, this runs every frame.
+        if app
+            .game_piece_layer
+            .warned_lengths
+            .insert((positions.len(), metadata.len()))
+        {
+            println!("Warning: {}", problem);
+        }
+    }
+
+    let target_id = match app.listened_values.get(nt_paths::TARGET_LUNITE_ID) {
+        Some(NTValueType::Double(id)) if *id >= 0.0 => Some(*id as i64),
+        _ => None,
+    };
+    let layer = &app.game_piece_layer;
+    let scale = (transform.points_per_meter() / 60.0).clamp(0.5, 3.0);
+
+    for piece in &pieces {
+        let center = transform.to_screen(piece.x, piece.y);
+        let fade = match piece.age {
+            Some(age) => (1.0 - age / layer.max_age_s).clamp(0.15, 1.0) as f32,
+            None => 1.0,
+        };
+        let radius = 5.0 * scale;
+        painter.circle_filled(
+            center,
+            radius,
+            Color32::from_rgb(0, 255, 0).gamma_multiply(fade),
+        );
+
+        let is_target = target_id.is_some() && piece.id == target_id;
+        if is_target {
+            painter.circle_stroke(
+                center,
+                radius + 4.0,
+                Stroke::new(2.5, Color32::from_rgb(255, 220, 0)),
+            );
+            if let Some((x, y, _)) = robot_pose(app) {
+                painter.line_segment(
+                    [transform.to_screen(x, y), center],
+                    Stroke::new(1.5, Color32::from_rgb(255, 220, 0).gamma_multiply(0.6)),
+                );
+            }
+        }
+
+        if layer.show_labels {
+            let mut label = String::new();
+            if let Some(id) = piece.id {
+                label.push_str(&format!("#{}", id));
+            }
+            if let Some(confidence) = piece.confidence {
+                label.push_str(&format!(" {:.0}%", confidence * 100.0));
+            }
+            if !label.is_empty() {
+                painter.text(
+                    center + egui::vec2(radius + 2.0, -radius - 2.0),
+                    Align2::LEFT_BOTTOM,
+                    label.trim(),
+                    FontId::proportional(12.0),
+                    Color32::WHITE.gamma_multiply(fade),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+
+use egui::{ComboBox, Context, DragValue, TextEdit, Ui, Window};
+use egui_plot::{Legend, Line, Plot, PlotBounds, PlotPoints};
+
+use crate::{
+    FrcUi,
+    nt_events::ValueUpdate,
+    nt_util::{ListenedValues, NTValueType},
+};
+
+/// Samples older than this get dropped, whatever the window is set to.
+const MAX_HISTORY_S: f64 = 600.0;
+
+pub fn as_number(value: &NTValueType) -> Option<f64> {
+    match value {
+        NTValueType::Double(d) => Some(*d),
+        NTValueType::Float(f) => Some(*f as f64),
+        NTValueType::Integer(i) => Some(*i as f64),
+        NTValueType::Boolean(b) => Some(*b as i32 as f64),
+        _ => None,
+    }
+}
+
+pub struct Series {
+    pub path: String,
+    pub unit: String,
+    /// (seconds, value), oldest first.
+    pub samples: VecDeque<(f64, f64)>,
+}
+
+impl Series {
+    fn new(path: String) -> Self {
+        Self {
+            path,
+            unit: String::new(),
+            samples: VecDeque::new(),
+        }
+    }
+
+    fn label(&self) -> String {
+        if self.unit.is_empty() {
+            self.path.clone()
+        } else {
+            format!("{} ({})", self.path, self.unit)
+        }
+    }
+}
+
+pub struct Graph {
+    pub series: Vec<Series>,
+    pub window_s: f64,
+    /// Stop following new data so it can be dragged/zoomed around.
+    pub paused: bool,
+    pub autoscale: bool,
+    pub y_min: f64,
+    pub y_max: f64,
+    /// Time we paused at, so the plot holds still while paused.
+    paused_at: Option<f64>,
+    new_path: String,
+}
+
+impl Default for Graph {
+    fn default() -> Self {
+        Self {
+            series: Vec::new(),
+            window_s: 10.0,
+            paused: false,
+            autoscale: true,
+            y_min: -1.0,
+            y_max: 1.0,
+            paused_at: None,
+            new_path: String::new(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Graphs {
+    pub open: bool,
+    pub graphs: Vec<Graph>,
+    /// Topics we've asked the source to listen to, so removed series can stop.
+    pub listening: Vec<String>,
+}
+
+impl Graphs {
+    /// Every topic some graph is plotting.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.graphs
+            .iter()
+            .flat_map(|g| g.series.iter().map(|s| s.path.as_str()))
+    }
+
+    /// Drops every sample, for when time jumps around (starting or seeking a replay).
+    pub fn clear_samples(&mut self) {
+        for graph in &mut self.graphs {
+            for series in &mut graph.series {
+                series.samples.clear();
+            }
+        }
+    }
+
+    /// Adds NT updates to the series that want them. `now_s` is the NT clock in seconds.
+    pub fn record(&mut self, updates: &[ValueUpdate], now_s: f64) {
+        for graph in &mut self.graphs {
+            for series in &mut graph.series {
+                for update in updates.iter().filter(|u| u.path == series.path) {
+                    if let Some(v) = as_number(&update.value) {
+                        series
+                            .samples
+                            .push_back((update.timestamp_us as f64 / 1e6, v));
+                    }
+                }
+                while series
+                    .samples
+                    .front()
+                    .is_some_and(|(t, _)| now_s - t > MAX_HISTORY_S)
+                {
+                    series.samples.pop_front();
+                }
+            }
+        }
+    }
+}
+
+fn graph_ui(ui: &mut Ui, graph: &mut Graph, index: usize, values: &ListenedValues, now_s: f64) {
+    ui.horizontal(|ui| {
+        ui.label("Window");
+        ui.add(
+            DragValue::new(&mut graph.window_s)
+                .range(1.0..=MAX_HISTORY_S)
+                .suffix(" s"),
+        );
+        ui.checkbox(&mut graph.paused, "Pause");
+        ui.checkbox(&mut graph.autoscale, "Autoscale");
+        if !graph.autoscale {
+            ui.add(DragValue::new(&mut graph.y_min).prefix("min "));
+            ui.add(DragValue::new(&mut graph.y_max).prefix("max "));
+        }
+    });
+    graph.paused_at = match (graph.paused, graph.paused_at) {
+        (true, None) => Some(now_s),
+        (true, at) => at,
+        (false, _) => None,
+    };
+    let now_s = graph.paused_at.unwrap_or(now_s);
+
+    let mut remove = None;
+    for (i, series) in graph.series.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(&series.path);
+            ui.label("unit");
+            ui.add(TextEdit::singleline(&mut series.unit).desired_width(50.0));
+            if ui.small_button("x").clicked() {
+                remove = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove {
+        graph.series.remove(i);
+    }
+
+    ui.horizontal(|ui| {
+        // Suggest numeric topics we already know about, but any path can be typed in.
+        let mut known: Vec<&String> = values
+            .iter()
+            .filter(|(_, v)| as_number(v).is_some())
+            .map(|(k, _)| k)
+            .collect();
+        known.sort();
+        ComboBox::new(("GraphTopic", index), "")
+            .selected_text("Topics")
+            .show_ui(ui, |ui| {
+                for path in known {
+                    ui.selectable_value(&mut graph.new_path, path.clone(), path);
+                }
+            });
+        ui.text_edit_singleline(&mut graph.new_path);
+        if ui.button("Add series").clicked() && !graph.new_path.is_empty() {
+            let path = std::mem::take(&mut graph.new_path);
+            if !graph.series.iter().any(|s| s.path == path) {
+                graph.series.push(Series::new(path));
+            }
+        }
+    });
+
+    // Times are relative to now so the x axis reads "seconds ago".
+    let plot = Plot::new(("Graph", index))
+        .height(200.0)
+        .legend(Legend::default())
+        .x_axis_label("s")
+        .allow_drag(graph.paused)
+        .allow_zoom(graph.paused)
+        .allow_scroll(graph.paused);
+    plot.show(ui, |plot_ui| {
+        if !graph.paused {
+            let (min_y, max_y) = if graph.autoscale {
+                let mut range = (f64::INFINITY, f64::NEG_INFINITY);
+                for series in &graph.series {
+                    for (t, v) in &series.samples {
+                        if now_s - t <= graph.window_s {
+                            range = (range.0.min(*v), range.1.max(*v));
+                        }
+                    }
+                }
+                if range.0 > range.1 {
+                    (-1.0, 1.0)
+                } else {
+                    // Some padding so lines don't sit on the edge.
+                    let pad = ((range.1 - range.0) * 0.05).max(1e-3);
+                    (range.0 - pad, range.1 + pad)
+                }
+            } else {
+                (graph.y_min, graph.y_max)
+            };
+            plot_ui.set_plot_bounds(PlotBounds::from_min_max(
+                [-graph.window_s, min_y],
+                [0.0, max_y],
+            ));
+        }
+        for series in &graph.series {
+            let points: PlotPoints = series
+                .samples
+                .iter()
+                .map(|(t, v)| [t - now_s, *v])
+                .collect();
+            plot_ui.line(Line::new(series.label(), points));
+        }
+    });
+}
+
+/// Floating window with all the graphs.
+pub fn graphs_window(ctx: &Context, app: &mut FrcUi, now_s: f64) {
+    let mut open = app.graphs.open;
+    Window::new("Graphs")
+        .open(&mut open)
+        .default_width(500.0)
+        .vscroll(true)
+        .show(ctx, |ui| {
+            if ui.button("Add graph").clicked() {
+                app.graphs.graphs.push(Graph::default());
+            }
+            let mut remove = None;
+            for (i, graph) in app.graphs.graphs.iter_mut().enumerate() {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.strong(format!("Graph {}", i + 1));
+                    if ui.small_button("Remove").clicked() {
+                        remove = Some(i);
+                    }
+                });
+                graph_ui(ui, graph, i, &app.listened_values, now_s);
+            }
+            if let Some(i) = remove {
+                app.graphs.graphs.remove(i);
+            }
+        });
+    app.graphs.open = open;
+}
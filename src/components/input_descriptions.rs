@@ -1,27 +1,153 @@
-use egui::Ui;
+use egui::{Align2, Color32, FontId, Pos2, Rect, Sense, Stroke, Ui, Vec2};
 
-use crate::{FrcUi, nt_paths, nt_util::NTValueType};
+use crate::{
+    FrcUi,
+    bindings::{Button, ControllerStyle},
+    nt_paths,
+    nt_util::NTValueType,
+};
 
-pub fn show_input_bindings(ui: &mut Ui, app: &mut FrcUi) {
-    if let Some(NTValueType::String(s)) = app.listened_values.get(nt_paths::CURRENT_STATE) {
-        if s == "IdleToIntake" {
-            ui.label("A: Do something | B: Do other thing | ...");
-        } else if s == "IdleToShoot" {
-            ui.label("A: Do something | B: Do other thing | ...");
-        } else if s == "Intake" {
-            ui.label("A: Do something | B: Do other thing | ...");
-        } else if s == "Shooting" {
-            ui.label("A: Do something | B: Do other thing | ...");
-        } else if s == "Autodrive" {
-            ui.label("A: Do something | B: Do other thing | ...");
-        } else if s == "IdleDebug" {
-            ui.label("A: Do something | B: Do other thing | ...");
-        } else if s == "ManualIntake" {
-            ui.label("A: Do something | B: Do other thing | ...");
+const DIAGRAM_SIZE: Vec2 = Vec2::new(260.0, 160.0);
+const HIGHLIGHT: Color32 = Color32::from_rgb(255, 180, 0);
+
+/// Where each button sits on the diagram, as a fraction of its size, and how big it is.
+fn button_layout(button: Button) -> (Pos2, Vec2) {
+    let round = Vec2::splat(0.07);
+    let (pos, size) = match button {
+        Button::LeftTrigger => (Pos2::new(0.22, 0.04), Vec2::new(0.12, 0.07)),
+        Button::RightTrigger => (Pos2::new(0.78, 0.04), Vec2::new(0.12, 0.07)),
+        Button::LeftBumper => (Pos2::new(0.22, 0.13), Vec2::new(0.16, 0.06)),
+        Button::RightBumper => (Pos2::new(0.78, 0.13), Vec2::new(0.16, 0.06)),
+        Button::LeftStick => (Pos2::new(0.27, 0.38), Vec2::splat(0.11)),
+        Button::RightStick => (Pos2::new(0.62, 0.62), Vec2::splat(0.11)),
+        Button::DpadUp => (Pos2::new(0.38, 0.54), Vec2::new(0.04, 0.06)),
+        Button::DpadDown => (Pos2::new(0.38, 0.70), Vec2::new(0.04, 0.06)),
+        Button::DpadLeft => (Pos2::new(0.34, 0.62), Vec2::new(0.04, 0.06)),
+        Button::DpadRight => (Pos2::new(0.42, 0.62), Vec2::new(0.04, 0.06)),
+        Button::Back => (Pos2::new(0.43, 0.36), Vec2::new(0.05, 0.05)),
+        Button::Start => (Pos2::new(0.57, 0.36), Vec2::new(0.05, 0.05)),
+        Button::Y => (Pos2::new(0.75, 0.27), round),
+        Button::A => (Pos2::new(0.75, 0.47), round),
+        Button::X => (Pos2::new(0.69, 0.37), round),
+        Button::B => (Pos2::new(0.81, 0.37), round),
+    };
+    (pos, size)
+}
+
+const ALL_BUTTONS: [Button; 16] = [
+    Button::A,
+    Button::B,
+    Button::X,
+    Button::Y,
+    Button::LeftBumper,
+    Button::RightBumper,
+    Button::LeftTrigger,
+    Button::RightTrigger,
+    Button::LeftStick,
+    Button::RightStick,
+    Button::Back,
+    Button::Start,
+    Button::DpadUp,
+    Button::DpadDown,
+    Button::DpadLeft,
+    Button::DpadRight,
+];
+
+/// Draws a gamepad and returns its rect. `fill` picks the color for each button,
+/// None for the default look.
+pub fn controller_diagram(
+    ui: &mut Ui,
+    style: ControllerStyle,
+    fill: impl Fn(Button) -> Option<Color32>,
+) -> Rect {
+    let (rect, _) = ui.allocate_exact_size(DIAGRAM_SIZE, Sense::hover());
+    let painter = ui.painter_at(rect);
+    let at = |p: Pos2| rect.min + p.to_vec2() * rect.size();
+
+    // Body and grips
+    let body_color = Color32::from_gray(45);
+    painter.rect_filled(
+        Rect::from_min_max(at(Pos2::new(0.1, 0.18)), at(Pos2::new(0.9, 0.75))),
+        20.0,
+        body_color,
+    );
+    for x in [0.2, 0.8] {
+        painter.circle_filled(at(Pos2::new(x, 0.75)), rect.width() * 0.11, body_color);
+    }
+
+    for button in ALL_BUTTONS {
+        let (pos, size) = button_layout(button);
+        let center = at(pos);
+        let size = size * rect.width();
+        let color = fill(button).unwrap_or(Color32::from_gray(90));
+        let is_round = matches!(
+            button,
+            Button::A | Button::B | Button::X | Button::Y | Button::LeftStick | Button::RightStick
+        );
+        if is_round {
+            painter.circle(
+                center,
+                size.x / 2.0,
+                color,
+                Stroke::new(1.0, Color32::from_gray(20)),
+            );
         } else {
-            ui.label("Unknown state - bindings will appear here, like A: Intake | ...");
+            painter.rect_filled(Rect::from_center_size(center, size), 2.0, color);
+        }
+        // Labels on the bigger buttons only, the dpad is too small.
+        if !matches!(
+            button,
+            Button::DpadUp | Button::DpadDown | Button::DpadLeft | Button::DpadRight
+        ) {
+            painter.text(
+                center,
+                Align2::CENTER_CENTER,
+                button.short_label(style),
+                FontId::proportional(10.0),
+                Color32::WHITE,
+            );
         }
-    } else {
-        ui.label("Unknown state - bindings will appear here, like A: Intake | ...");
     }
+    rect
+}
+
+pub fn show_input_bindings(ui: &mut Ui, app: &mut FrcUi) {
+    let state = match app.listened_values.get(nt_paths::CURRENT_STATE) {
+        Some(NTValueType::String(s)) => s.as_str(),
+        _ => "Unknown",
+    };
+    let (config, from_robot) = app.bindings.active();
+    let bindings = config.for_state(state);
+
+    ui.horizontal(|ui| {
+        controller_diagram(ui, config.controller, |button| {
+            bindings
+                .iter()
+                .any(|b| b.button == button)
+                .then_some(HIGHLIGHT)
+        });
+        ui.vertical(|ui| {
+            ui.weak(format!(
+                "Bindings for {} ({})",
+                state,
+                if from_robot {
+                    "from robot"
+                } else {
+                    "from file"
+                }
+            ));
+            if let Some(e) = app.bindings.nt_error() {
+                ui.colored_label(Color32::from_rgb(255, 120, 0), e);
+            }
+            if bindings.is_empty() {
+                ui.label("No bindings for this state.");
+            }
+            for binding in bindings {
+                ui.horizontal(|ui| {
+                    ui.colored_label(HIGHLIGHT, binding.button.label(config.controller));
+                    ui.label(&binding.action);
+                });
+            }
+        });
+    });
 }
@@ -0,0 +1,147 @@
+use egui::{Color32, ComboBox, Rect, Sense, Stroke, Ui, Vec2};
+
+use crate::{
+    FrcUi,
+    bindings::{Button, ControllerStyle},
+    components::input_descriptions::controller_diagram,
+    joysticks::{JoystickSource, JoystickState, Joysticks, PORT_COUNT},
+};
+
+const PRESSED: Color32 = Color32::from_rgb(0, 200, 80);
+const XBOX_AXIS_NAMES: [&str; 6] = ["LX", "LY", "LT", "RT", "RX", "RY"];
+
+/// Maps the DS view of an Xbox controller back onto the diagram buttons.
+fn is_pressed(state: &JoystickState, button: Button) -> bool {
+    let pov = state.pov();
+    let pov_near = |angle: i32| {
+        pov >= 0 && ((pov - angle + 360) % 360 <= 45 || (angle - pov + 360) % 360 <= 45)
+    };
+    match button {
+        Button::A => state.button(1),
+        Button::B => state.button(2),
+        Button::X => state.button(3),
+        Button::Y => state.button(4),
+        Button::LeftBumper => state.button(5),
+        Button::RightBumper => state.button(6),
+        Button::Back => state.button(7),
+        Button::Start => state.button(8),
+        Button::LeftStick => state.button(9),
+        Button::RightStick => state.button(10),
+        Button::LeftTrigger => state.axis(2) > 0.5,
+        Button::RightTrigger => state.axis(3) > 0.5,
+        Button::DpadUp => pov_near(0),
+        Button::DpadRight => pov_near(90),
+        Button::DpadDown => pov_near(180),
+        Button::DpadLeft => pov_near(270),
+    }
+}
+
+fn axis_bar(ui: &mut Ui, value: f64) {
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(100.0, 10.0), Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, Color32::from_gray(50));
+    let center = rect.center().x;
+    let end = center + value.clamp(-1.0, 1.0) as f32 * rect.width() / 2.0;
+    painter.rect_filled(
+        Rect::from_x_y_ranges(center.min(end)..=center.max(end), rect.y_range()),
+        0.0,
+        PRESSED,
+    );
+    painter.vline(
+        center,
+        rect.y_range(),
+        Stroke::new(1.0, Color32::from_gray(120)),
+    );
+}
+
+fn pov_dial(ui: &mut Ui, pov: i32) {
+    let (rect, _) = ui.allocate_exact_size(Vec2::splat(28.0), Sense::hover());
+    let painter = ui.painter_at(rect);
+    let radius = rect.width() / 2.0 - 1.0;
+    painter.circle_stroke(
+        rect.center(),
+        radius,
+        Stroke::new(1.0, Color32::from_gray(120)),
+    );
+    if pov >= 0 {
+        // 0 is up, clockwise.
+        let dir = Vec2::angled((pov as f32 - 90.0).to_radians());
+        painter.line_segment(
+            [rect.center(), rect.center() + dir * radius],
+            Stroke::new(2.0, PRESSED),
+        );
+    }
+}
+
+pub fn joystick_panel(ui: &mut Ui, app: &mut FrcUi) {
+    app.joysticks.update_local();
+
+    ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ComboBox::new("JoystickPort", "Port")
+                .selected_text(app.joysticks.port.to_string())
+                .show_ui(ui, |ui| {
+                    for port in 0..PORT_COUNT {
+                        ui.selectable_value(&mut app.joysticks.port, port, port.to_string());
+                    }
+                });
+            if Joysticks::local_available() {
+                ui.selectable_value(&mut app.joysticks.source, JoystickSource::Robot, "Robot");
+                ui.selectable_value(
+                    &mut app.joysticks.source,
+                    JoystickSource::Local,
+                    "This laptop",
+                );
+            }
+        });
+
+        let state = app.joysticks.state(&app.listened_values);
+        if state.is_empty() {
+            ui.weak(match app.joysticks.source {
+                JoystickSource::Robot => "Robot isn't sending this port.",
+                JoystickSource::Local => "No gamepad on this port.",
+            });
+            return;
+        }
+        if !state.name.is_empty() {
+            ui.weak(&state.name);
+        }
+
+        // Pictures only make sense for gamepads, joysticks just get the numbers.
+        if state.axes.len() == 6 {
+            controller_diagram(ui, ControllerStyle::Xbox, |button| {
+                is_pressed(&state, button).then_some(PRESSED)
+            });
+        }
+
+        for (i, value) in state.axes.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let name = if state.axes.len() == 6 {
+                    XBOX_AXIS_NAMES[i].to_string()
+                } else {
+                    format!("Axis {}", i)
+                };
+                ui.label(name);
+                axis_bar(ui, *value);
+                ui.weak(format!("{:+.2}", value));
+            });
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            for (i, pressed) in state.buttons.iter().enumerate() {
+                let text = (i + 1).to_string();
+                if *pressed {
+                    ui.colored_label(PRESSED, text);
+                } else {
+                    ui.weak(text);
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            for pov in &state.povs {
+                pov_dial(ui, *pov);
+            }
+        });
+    });
+}
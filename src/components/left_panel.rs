@@ -11,12 +11,16 @@ use crate::FrcUi;
 pub fn left_panel(ctx: &egui::Context, app: &mut FrcUi) {
     SidePanel::left("LeftCamerasPanel").show(ctx, |ui| {
         ui.vertical(|ui| {
-            for (name, capture) in &mut app.camera_streams {
+            // Recordings stand in for the live cameras while replaying.
+            let streams = match &mut app.replay {
+                Some(replay) => &mut replay.cameras,
+                None => &mut app.camera_streams,
+            };
+            for (name, capture) in streams {
                 ui.weak(format!("Camera Feed: {}", name));
                 let mut mat = Mat::default();
                 // If capture failed, show a blank image.
                 let capture_status = capture.read(&mut mat);
-                println!("{:?}", capture_status);
                 if capture_status.ok().filter(|b| *b).is_none() {
                     // Black screen
                     // mat = Mat::zeros(480, 640, CV_8UC3)
@@ -42,7 +46,6 @@ pub fn left_panel(ctx: &egui::Context, app: &mut FrcUi) {
                     unsafe { slice_from_raw_parts(mat.data(), mat.total() * 3).as_ref() };
                 if let Some(slice) = unsafe_slice {
                     // let mjpg_res = app.m.update_jpeg(slice.iter().cloned().collect());
-                    // println!("{:?}", mjpg_res);
 
                     let image =
                         ColorImage::from_rgb([mat.cols() as usize, mat.rows() as usize], slice);
@@ -0,0 +1,133 @@
+use egui::{Color32, CornerRadius, DragValue, FontId, Frame, RichText, Ui};
+
+use crate::{
+    FrcUi,
+    fms::{MatchPhase, control_word},
+    nt_paths,
+    nt_util::{NTValueType, format_game_time},
+};
+
+const FLASH_DURATION_S: f64 = 2.0;
+const FLASH_PERIOD_S: f64 = 0.25;
+
+pub struct MatchTimer {
+    pub endgame_start_s: f64,
+    /// Seconds left at which to alert, counting down.
+    pub alert_thresholds_s: Vec<f64>,
+    pub flash: bool,
+    last_time_left: Option<f64>,
+    flash_until: f64,
+}
+
+impl Default for MatchTimer {
+    fn default() -> Self {
+        Self {
+            endgame_start_s: 20.0,
+            alert_thresholds_s: vec![30.0, 20.0, 10.0],
+            flash: true,
+            last_time_left: None,
+            flash_until: 0.0,
+        }
+    }
+}
+
+/// Match time left, or None if we're not in a match. The robot publishes -1 then.
+pub fn time_left(app: &FrcUi) -> Option<f64> {
+    match app.listened_values.get(nt_paths::GAME_TIME) {
+        Some(NTValueType::Double(t)) if *t >= 0.0 => Some(*t),
+        _ => None,
+    }
+}
+
+pub fn current_phase(app: &FrcUi) -> MatchPhase {
+    MatchPhase::from_control_word(
+        control_word(app).unwrap_or_default(),
+        time_left(app),
+        app.match_timer.endgame_start_s,
+    )
+}
+
+pub fn phase_color(phase: MatchPhase) -> Color32 {
+    match phase {
+        MatchPhase::Disabled => Color32::from_gray(160),
+        MatchPhase::Auto => Color32::from_rgb(80, 170, 255),
+        MatchPhase::Teleop => Color32::from_rgb(80, 220, 80),
+        MatchPhase::Endgame => Color32::from_rgb(255, 180, 0),
+        MatchPhase::Test => Color32::from_rgb(200, 120, 255),
+        MatchPhase::EStopped => Color32::from_rgb(255, 40, 40),
+    }
+}
+
+pub fn match_timer(ui: &mut Ui, app: &mut FrcUi) {
+    let time_left = time_left(app);
+    let phase = current_phase(app);
+    let now = ui.input(|i| i.time);
+
+    // Alert when we count down past a threshold. Only while enabled so a robot
+    // sitting disabled with a stale time doesn't keep alerting.
+    if let (Some(prev), Some(cur)) = (app.match_timer.last_time_left, time_left) {
+        let crossed = app
+            .match_timer
+            .alert_thresholds_s
+            .iter()
+            .any(|t| prev > *t && cur <= *t);
+        if crossed && phase != MatchPhase::Disabled && app.match_timer.flash {
+            app.match_timer.flash_until = now + FLASH_DURATION_S;
+        }
+    }
+    app.match_timer.last_time_left = time_left;
+
+    let color = phase_color(phase);
+    let flashing = now < app.match_timer.flash_until;
+    let flash_on = flashing && (now / FLASH_PERIOD_S) as i64 % 2 == 0;
+    let (fill, text_color) = if flash_on {
+        (color, Color32::BLACK)
+    } else {
+        (Color32::TRANSPARENT, color)
+    };
+
+    Frame::new()
+        .fill(fill)
+        .corner_radius(CornerRadius::same(4))
+        .inner_margin(4)
+        .show(ui, |ui| {
+            ui.label(
+                RichText::new(format_game_time(time_left))
+                    .font(FontId::proportional(30.0))
+                    .color(text_color),
+            );
+            ui.label(
+                RichText::new(phase.name())
+                    .font(FontId::proportional(20.0))
+                    .color(text_color),
+            );
+        });
+}
+
+pub fn match_timer_settings(ui: &mut Ui, timer: &mut MatchTimer) {
+    ui.horizontal(|ui| {
+        ui.label("Endgame starts at: ");
+        ui.add(
+            DragValue::new(&mut timer.endgame_start_s)
+                .range(0.0..=135.0)
+                .suffix(" s"),
+        );
+        ui.checkbox(&mut timer.flash, "Flash");
+    });
+    ui.horizontal_wrapped(|ui| {
+        ui.label("Alert at: ");
+        let mut remove = None;
+        for (i, threshold) in timer.alert_thresholds_s.iter_mut().enumerate() {
+            ui.add(DragValue::new(threshold).range(0.0..=150.0).suffix(" s"));
+            if ui.small_button("x").clicked() {
+                remove = Some(i);
+            }
+        }
+        if let Some(i) = remove {
+            timer.alert_thresholds_s.remove(i);
+        }
+        if ui.small_button("+").clicked() {
+            timer.alert_thresholds_s.push(timer.endgame_start_s);
+        }
+    });
+}
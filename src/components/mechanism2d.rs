@@ -0,0 +1,180 @@
+use egui::{Align2, Color32, FontId, Painter, Pos2, Sense, Stroke, Ui, Vec2};
+
+use crate::{FrcUi, nt_paths, nt_util::NTValueType};
+
+/// A WPILib Mechanism2d, rebuilt from its topics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mechanism2d {
+    pub width: f64,
+    pub height: f64,
+    pub background: Color32,
+    pub roots: Vec<MechanismRoot>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MechanismRoot {
+    pub name: String,
+    pub x: f64,
+    pub y: f64,
+    pub ligaments: Vec<Ligament>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ligament {
+    pub name: String,
+    pub length: f64,
+    /// Degrees, relative to the parent.
+    pub angle: f64,
+    /// Line width in pixels.
+    pub weight: f64,
+    pub color: Color32,
+    pub children: Vec<Ligament>,
+}
+
+fn double(value: Option<&NTValueType>, default: f64) -> f64 {
+    match value {
+        Some(NTValueType::Double(d)) => *d,
+        Some(NTValueType::Float(f)) => *f as f64,
+        Some(NTValueType::Integer(i)) => *i as f64,
+        _ => default,
+    }
+}
+
+fn color(value: Option<&NTValueType>, default: Color32) -> Color32 {
+    match value {
+        Some(NTValueType::String(s)) => Color32::from_hex(s).unwrap_or(default),
+        _ => default,
+    }
+}
+
+/// Direct children of `parent` ("" for the top level) that have a key called `marker`.
+fn children<'a>(keys: &'a [String], parent: &str, marker: &str) -> Vec<&'a str> {
+    let mut found: Vec<&str> = keys
+        .iter()
+        .filter_map(|k| k.strip_suffix(marker))
+        .filter_map(|path| {
+            let rest = if parent.is_empty() {
+                path
+            } else {
+                path.strip_prefix(parent)?.strip_prefix('/')?
+            };
+            (!rest.is_empty() && !rest.contains('/')).then_some(rest)
+        })
+        .collect();
+    found.sort();
+    found.dedup();
+    found
+}
+
+fn parse_ligaments<'a>(
+    keys: &[String],
+    parent: &str,
+    get: &impl Fn(&str) -> Option<&'a NTValueType>,
+) -> Vec<Ligament> {
+    children(keys, parent, "/.type")
+        .into_iter()
+        .map(|name| {
+            let path = format!("{}/{}", parent, name);
+            let key = |k: &str| get(&format!("{}/{}", path, k));
+            Ligament {
+                name: name.to_string(),
+                length: double(key("length"), 0.0),
+                angle: double(key("angle"), 0.0),
+                weight: double(key("weight"), 6.0),
+                color: color(key("color"), Color32::from_rgb(235, 137, 52)),
+                children: parse_ligaments(keys, &path, get),
+            }
+        })
+        .collect()
+}
+
+/// `keys` are the topics under the Mechanism2d's table, `get` looks one of them up.
+pub fn parse_mechanism<'a>(
+    keys: &[String],
+    get: impl Fn(&str) -> Option<&'a NTValueType>,
+) -> Mechanism2d {
+    let (width, height) = match get("dims") {
+        Some(NTValueType::DoubleArray(dims)) if dims.len() >= 2 => (dims[0], dims[1]),
+        _ => (1.0, 1.0),
+    };
+    let roots = children(keys, "", "/x")
+        .into_iter()
+        .map(|name| MechanismRoot {
+            name: name.to_string(),
+            x: double(get(&format!("{}/x", name)), 0.0),
+            y: double(get(&format!("{}/y", name)), 0.0),
+            ligaments: parse_ligaments(keys, name, &get),
+        })
+        .collect();
+    Mechanism2d {
+        width,
+        height,
+        background: color(get("backgroundColor"), Color32::from_rgb(0, 0, 32)),
+        roots,
+    }
+}
+
+fn draw_ligaments(
+    painter: &Painter,
+    to_screen: &impl Fn(f64, f64) -> Pos2,
+    ligaments: &[Ligament],
+    (x, y): (f64, f64),
+    parent_angle: f64,
+) {
+    for ligament in ligaments {
+        let angle = parent_angle + ligament.angle.to_radians();
+        let end = (
+            x + ligament.length * angle.cos(),
+            y + ligament.length * angle.sin(),
+        );
+        painter.line_segment(
+            [to_screen(x, y), to_screen(end.0, end.1)],
+            Stroke::new(ligament.weight as f32, ligament.color),
+        );
+        draw_ligaments(painter, to_screen, &ligament.children, end, angle);
+    }
+}
+
+pub fn mechanism2d_widget(ui: &mut Ui, app: &mut FrcUi, table: &str) {
+    let keys = app
+        .sendables
+        .list
+        .iter()
+        .find(|s| s.table == table)
+        .map(|s| s.keys.as_slice())
+        .unwrap_or_default();
+    let mechanism = parse_mechanism(keys, |key| {
+        app.listened_values.get(&nt_paths::sendable_key(table, key))
+    });
+    if mechanism.width <= 0.0 || mechanism.height <= 0.0 {
+        ui.weak("Bad mechanism size");
+        return;
+    }
+
+    let width = ui.available_width().min(300.0);
+    let size = Vec2::new(width, width * (mechanism.height / mechanism.width) as f32);
+    let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, mechanism.background);
+
+    // Mechanism units, y up.
+    let scale = rect.width() as f64 / mechanism.width;
+    let to_screen = |x: f64, y: f64| {
+        Pos2::new(
+            rect.left() + (x * scale) as f32,
+            rect.bottom() - (y * scale) as f32,
+        )
+    };
+    for root in &mechanism.roots {
+        draw_ligaments(&painter, &to_screen, &root.ligaments, (root.x, root.y), 0.0);
+    }
+    if mechanism.roots.is_empty() {
+        painter.text(
+            rect.center(),
+            Align2::CENTER_CENTER,
+            "No roots",
+            FontId::proportional(12.0),
+            Color32::GRAY,
+        );
+    }
+}
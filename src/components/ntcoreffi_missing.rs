@@ -0,0 +1,43 @@
+use egui::{CentralPanel, Color32, RichText};
+
+const DOWNLOAD_URL: &str = "https://frcmaven.wpi.edu/artifactory/release/edu/wpi/first/ntcoreffi/";
+
+/// Shown instead of the dashboard when ntcoreffi couldn't be loaded at startup, so people get
+/// told what's missing instead of the app just not opening.
+pub struct NtcoreffiMissing {
+    error: String,
+}
+
+impl NtcoreffiMissing {
+    pub fn new(error: String) -> Self {
+        Self { error }
+    }
+}
+
+impl eframe::App for NtcoreffiMissing {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.heading(RichText::new("ntcoreffi not found").color(Color32::from_rgb(230, 60, 60)));
+            ui.label(
+                "The dashboard talks to the robot through WPILib's ntcoreffi library, and couldn't find it.",
+            );
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                ui.label("Download the ntcoreffi binary zip for your platform (2025.3.2) from");
+                ui.hyperlink_to("WPILib's maven", DOWNLOAD_URL);
+            });
+            ui.label(format!(
+                "and put {} in one of these places, then restart:",
+                ntcore_sys::library_file_name()
+            ));
+            for path in ntcore_sys::search_paths() {
+                ui.monospace(path.display().to_string());
+            }
+            ui.label("(the first one is NTCOREFFI_PATH if it's set, the last one means anywhere the system looks for libraries)");
+            ui.add_space(8.0);
+            ui.collapsing("Details", |ui| {
+                ui.monospace(&self.error);
+            });
+        });
+    }
+}
@@ -0,0 +1,110 @@
+use std::path::Path;
+
+use egui::{Color32, ComboBox, Slider, TopBottomPanel, Ui};
+
+use crate::{
+    FrcUi,
+    replay::{Replay, SPEEDS, list_logs},
+};
+
+fn format_time(seconds: f64) -> String {
+    let seconds = seconds.max(0.0);
+    format!("{}:{:04.1}", (seconds / 60.0) as u64, seconds % 60.0)
+}
+
+pub fn start_replay(app: &mut FrcUi, path: &Path) {
+    let camera_names: Vec<String> = app.camera_ips.keys().cloned().collect();
+    match Replay::load(path, &camera_names) {
+        Ok(replay) => {
+            println!("Replaying {}", path.display());
+            // Don't mix live values into the recording.
+            app.listened_values.clear();
+            app.graphs.clear_samples();
+            app.replay = Some(replay);
+        }
+        Err(e) => println!("Failed to start replay: {}", e),
+    }
+}
+
+pub fn stop_replay(app: &mut FrcUi) {
+    app.replay = None;
+    app.listened_values.clear();
+    app.graphs.clear_samples();
+}
+
+/// "Replay" menu for the bottom bar: recent logs from the logging folder, or any path.
+pub fn replay_menu(ui: &mut Ui, app: &mut FrcUi) {
+    let mut open = None;
+    ui.menu_button("Replay", |ui| {
+        let logs = list_logs(Path::new(&app.logger.dir));
+        if logs.is_empty() {
+            ui.weak(format!("No logs in {}", app.logger.dir));
+        }
+        for log in logs.into_iter().take(15) {
+            let name = log.file_name().unwrap_or_default().to_string_lossy();
+            if ui.button(name.as_ref()).clicked() {
+                open = Some(log.clone());
+            }
+        }
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut app.replay_path);
+            if ui.button("Open").clicked() {
+                open = Some(app.replay_path.clone().into());
+            }
+        });
+    });
+    if let Some(path) = open {
+        start_replay(app, &path);
+    }
+}
+
+/// Timeline with play/pause/speed while replaying a log. Shows nothing when live.
+pub fn replay_bar(ctx: &egui::Context, app: &mut FrcUi) {
+    let Some(replay) = &mut app.replay else {
+        return;
+    };
+    let mut stop = false;
+    TopBottomPanel::bottom("ReplayPanel").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            ui.colored_label(Color32::from_rgb(255, 160, 0), "REPLAY");
+            ui.label(replay.file_name());
+            let duration = replay.duration_s();
+            let play_text = if replay.playing { "⏸" } else { "▶" };
+            if ui.button(play_text).clicked() {
+                // Start over if we're at the end.
+                if !replay.playing && replay.elapsed_s() >= duration {
+                    replay.seek_s(0.0);
+                }
+                replay.playing = !replay.playing;
+            }
+            ComboBox::new("ReplaySpeed", "")
+                .selected_text(format!("{}x", replay.speed))
+                .show_ui(ui, |ui| {
+                    for speed in SPEEDS {
+                        ui.selectable_value(&mut replay.speed, speed, format!("{}x", speed));
+                    }
+                });
+
+            let mut elapsed = replay.elapsed_s();
+            ui.spacing_mut().slider_width = (ui.available_width() - 250.0).max(100.0);
+            if ui
+                .add(Slider::new(&mut elapsed, 0.0..=duration).show_value(false))
+                .changed()
+            {
+                replay.seek_s(elapsed);
+            }
+            ui.label(format!(
+                "{} / {}",
+                format_time(elapsed),
+                format_time(duration)
+            ));
+            if ui.button("Back to live").clicked() {
+                stop = true;
+            }
+        });
+    });
+    if stop {
+        stop_replay(app);
+    }
+}
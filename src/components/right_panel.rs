@@ -0,0 +1,19 @@
+use egui::{CollapsingHeader, ScrollArea, SidePanel};
+
+use crate::{
+    FrcUi,
+    components::{sendable_widgets::sendables_list, state_graph::state_graph},
+};
+
+pub fn right_panel(ctx: &egui::Context, app: &mut FrcUi) {
+    SidePanel::right("RightPanel").show(ctx, |ui| {
+        ScrollArea::vertical().show(ui, |ui| {
+            CollapsingHeader::new("State machine")
+                .default_open(true)
+                .show(ui, |ui| state_graph(ui, app));
+            CollapsingHeader::new("SmartDashboard")
+                .default_open(true)
+                .show(ui, |ui| sendables_list(ui, app));
+        });
+    });
+}
@@ -0,0 +1,225 @@
+use egui::{CollapsingHeader, Color32, DragValue, Grid, Sense, Slider, Stroke, Ui, Vec2};
+
+use crate::{
+    FrcUi,
+    components::{
+        alerts::{ALERTS_TYPE, alerts_widget},
+        chooser::{CHOOSER_TYPE, chooser},
+        mechanism2d::mechanism2d_widget,
+    },
+    nt_paths,
+    nt_util::NTValueType,
+};
+
+/// Draws one Sendable, given its table.
+pub type SendableWidget = fn(&mut Ui, &mut FrcUi, &str);
+
+/// Widgets for the `.type`s we know. Anything else gets the generic key/value list.
+const WIDGETS: &[(&str, SendableWidget)] = &[
+    ("Command", command_widget),
+    ("Subsystem", subsystem_widget),
+    ("PIDController", pid_widget),
+    ("Gyro", gyro_widget),
+    ("DifferentialDrive", differential_drive_widget),
+    ("Mechanism2d", mechanism2d_widget),
+    (ALERTS_TYPE, alerts_widget),
+    (CHOOSER_TYPE, chooser_widget),
+];
+
+pub fn widget_for(type_name: &str) -> SendableWidget {
+    WIDGETS
+        .iter()
+        .find(|(t, _)| *t == type_name)
+        .map(|(_, w)| *w)
+        .unwrap_or(generic_widget)
+}
+
+fn value<'a>(app: &'a FrcUi, table: &str, key: &str) -> Option<&'a NTValueType> {
+    app.listened_values.get(&nt_paths::sendable_key(table, key))
+}
+
+fn string<'a>(app: &'a FrcUi, table: &str, key: &str) -> &'a str {
+    match value(app, table, key) {
+        Some(NTValueType::String(s)) => s,
+        _ => "",
+    }
+}
+
+fn double(app: &FrcUi, table: &str, key: &str) -> Option<f64> {
+    match value(app, table, key) {
+        Some(NTValueType::Double(d)) => Some(*d),
+        Some(NTValueType::Float(f)) => Some(*f as f64),
+        Some(NTValueType::Integer(i)) => Some(*i as f64),
+        _ => None,
+    }
+}
+
+fn boolean(app: &FrcUi, table: &str, key: &str) -> bool {
+    matches!(value(app, table, key), Some(NTValueType::Boolean(true)))
+}
+
+fn set_double(app: &mut FrcUi, table: &str, key: &str, v: f64) {
+    app.data_mut()
+        .publish(&nt_paths::sendable_key(table, key), NTValueType::Double(v));
+}
+
+fn set_boolean(app: &mut FrcUi, table: &str, key: &str, v: bool) {
+    app.data_mut()
+        .publish(&nt_paths::sendable_key(table, key), NTValueType::Boolean(v));
+}
+
+/// Editable double that writes back to NT when the user changes it.
+fn double_field(ui: &mut Ui, app: &mut FrcUi, table: &str, key: &str, label: &str) {
+    ui.label(label);
+    match double(app, table, key) {
+        Some(mut v) => {
+            let response = ui.add(DragValue::new(&mut v).speed(0.001).max_decimals(5));
+            if response.changed() {
+                set_double(app, table, key, v);
+            }
+        }
+        None => {
+            ui.weak("-");
+        }
+    }
+    ui.end_row();
+}
+
+fn format_value(value: &NTValueType) -> String {
+    match value {
+        NTValueType::Unknown => String::from("-"),
+        NTValueType::Boolean(b) => b.to_string(),
+        NTValueType::Double(d) => format!("{:.3}", d),
+        NTValueType::Float(f) => format!("{:.3}", f),
+        NTValueType::Integer(i) => i.to_string(),
+        NTValueType::String(s) => s.clone(),
+        other => format!("{:?}", other),
+    }
+}
+
+fn generic_widget(ui: &mut Ui, app: &mut FrcUi, table: &str) {
+    let keys = app
+        .sendables
+        .list
+        .iter()
+        .find(|s| s.table == table)
+        .map(|s| s.keys.clone())
+        .unwrap_or_default();
+    Grid::new(table).striped(true).show(ui, |ui| {
+        for key in keys.iter().filter(|k| !k.starts_with('.')) {
+            ui.label(key);
+            ui.label(value(app, table, key).map(format_value).unwrap_or_default());
+            ui.end_row();
+        }
+    });
+}
+
+fn command_widget(ui: &mut Ui, app: &mut FrcUi, table: &str) {
+    let running = boolean(app, table, "running");
+    ui.horizontal(|ui| {
+        let text = if running { "Cancel" } else { "Run" };
+        if ui.button(text).clicked() {
+            set_boolean(app, table, "running", !running);
+        }
+        if running {
+            ui.colored_label(Color32::from_rgb(0, 200, 80), "running");
+        } else {
+            ui.weak("idle");
+        }
+        if boolean(app, table, ".isParented") {
+            ui.weak("(in a group)");
+        }
+    });
+}
+
+fn subsystem_widget(ui: &mut Ui, app: &mut FrcUi, table: &str) {
+    Grid::new(table).show(ui, |ui| {
+        ui.label("Default");
+        ui.label(if boolean(app, table, ".hasDefault") {
+            string(app, table, ".default")
+        } else {
+            "none"
+        });
+        ui.end_row();
+        ui.label("Current");
+        ui.label(if boolean(app, table, ".hasCommand") {
+            string(app, table, ".command")
+        } else {
+            "none"
+        });
+        ui.end_row();
+    });
+}
+
+fn pid_widget(ui: &mut Ui, app: &mut FrcUi, table: &str) {
+    Grid::new(table).show(ui, |ui| {
+        double_field(ui, app, table, "p", "P");
+        double_field(ui, app, table, "i", "I");
+        double_field(ui, app, table, "d", "D");
+        if value(app, table, "izone").is_some() {
+            double_field(ui, app, table, "izone", "I zone");
+        }
+        double_field(ui, app, table, "setpoint", "Setpoint");
+    });
+}
+
+fn gyro_widget(ui: &mut Ui, app: &mut FrcUi, table: &str) {
+    let Some(angle) = double(app, table, "Value") else {
+        ui.weak("No value");
+        return;
+    };
+    ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size(Vec2::splat(60.0), Sense::hover());
+        let painter = ui.painter_at(rect);
+        let radius = rect.width() / 2.0 - 2.0;
+        painter.circle_stroke(
+            rect.center(),
+            radius,
+            Stroke::new(1.5, Color32::from_gray(160)),
+        );
+        // Gyros are clockwise positive, 0 pointing up.
+        let dir = Vec2::angled((angle as f32 - 90.0).to_radians());
+        painter.arrow(
+            rect.center(),
+            dir * radius,
+            Stroke::new(2.0, Color32::from_rgb(255, 180, 0)),
+        );
+        ui.label(format!("{:.1}°", angle));
+    });
+}
+
+fn differential_drive_widget(ui: &mut Ui, app: &mut FrcUi, table: &str) {
+    for (key, label) in [("Left Motor Speed", "Left"), ("Right Motor Speed", "Right")] {
+        let mut speed = double(app, table, key).unwrap_or(0.0);
+        ui.horizontal(|ui| {
+            ui.label(label);
+            // The robot only listens to these in test mode.
+            if ui.add(Slider::new(&mut speed, -1.0..=1.0)).changed() {
+                set_double(app, table, key, speed);
+            }
+        });
+    }
+}
+
+fn chooser_widget(ui: &mut Ui, app: &mut FrcUi, table: &str) {
+    chooser(ui, app, table, "");
+}
+
+/// Every Sendable the robot put on SmartDashboard, each with its widget.
+pub fn sendables_list(ui: &mut Ui, app: &mut FrcUi) {
+    let sendables: Vec<(String, String, String)> = app
+        .sendables
+        .list
+        .iter()
+        .map(|s| (s.table.clone(), s.name().to_string(), s.type_name.clone()))
+        .collect();
+    if sendables.is_empty() {
+        ui.weak("Nothing on SmartDashboard yet.");
+        return;
+    }
+    for (table, name, type_name) in sendables {
+        CollapsingHeader::new(format!("{} ({})", name, type_name))
+            .id_salt(&table)
+            .show(ui, |ui| widget_for(&type_name)(ui, app, &table));
+    }
+}
@@ -0,0 +1,146 @@
+use std::f32::consts::TAU;
+
+use egui::{
+    Align2, Color32, FontId, Pos2, Rect, ScrollArea, Sense, Stroke, Ui, Vec2, emath::easing,
+};
+
+use crate::FrcUi;
+
+const NODE_RADIUS: f32 = 26.0;
+const TRANSITION_ANIMATION_S: f64 = 0.5;
+const ACTIVE_COLOR: Color32 = Color32::from_rgb(255, 180, 0);
+
+fn node_positions(count: usize, rect: Rect) -> Vec<Pos2> {
+    let radius = (rect.width().min(rect.height()) / 2.0 - NODE_RADIUS - 4.0).max(10.0);
+    (0..count)
+        .map(|i| {
+            // Start at the top and go clockwise.
+            let angle = i as f32 / count as f32 * TAU - TAU / 4.0;
+            rect.center() + Vec2::angled(angle) * radius
+        })
+        .collect()
+}
+
+fn short_name(state: &str) -> String {
+    if state.chars().count() > 10 {
+        format!("{}…", state.chars().take(9).collect::<String>())
+    } else {
+        state.to_string()
+    }
+}
+
+pub fn state_graph(ui: &mut Ui, app: &FrcUi) {
+    let config = &app.state_machine;
+    if config.states.is_empty() {
+        ui.weak("No state machine config loaded.");
+        return;
+    }
+
+    let size = Vec2::splat(ui.available_width().min(320.0));
+    let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+    let painter = ui.painter_at(rect);
+    let positions = node_positions(config.states.len(), rect);
+    let now = ui.input(|i| i.time);
+    let current = app.state_history.current();
+
+    for (from, to) in &config.transitions {
+        if let (Some(a), Some(b)) = (config.index_of(from), config.index_of(to)) {
+            let (a, b) = (positions[a], positions[b]);
+            let dir = (b - a).normalized();
+            let start = a + dir * NODE_RADIUS;
+            let end = b - dir * NODE_RADIUS;
+            painter.arrow(
+                start,
+                end - start,
+                Stroke::new(1.0, Color32::from_gray(120)),
+            );
+        }
+    }
+
+    // Animate a dot along the edge we just took.
+    let mut animating = false;
+    if let (Some(cur), Some(prev)) = (current, app.state_history.previous()) {
+        let t = (now - cur.entered_at) / TRANSITION_ANIMATION_S;
+        if t < 1.0
+            && let (Some(a), Some(b)) = (config.index_of(&prev.state), config.index_of(&cur.state))
+        {
+            let t = easing::cubic_in_out(t as f32);
+            let pos = positions[a] + (positions[b] - positions[a]) * t;
+            painter.circle_filled(pos, 6.0, ACTIVE_COLOR);
+            animating = true;
+        }
+    }
+    if animating {
+        ui.ctx().request_repaint();
+    }
+
+    for (i, state) in config.states.iter().enumerate() {
+        let is_active = current.is_some_and(|c| &c.state == state);
+        let fill = if is_active {
+            // Fade the highlight in as the transition dot arrives.
+            let t = current
+                .map(|c| ((now - c.entered_at) / TRANSITION_ANIMATION_S).clamp(0.0, 1.0))
+                .unwrap_or(1.0) as f32;
+            Color32::from_gray(50).lerp_to_gamma(ACTIVE_COLOR, t)
+        } else {
+            Color32::from_gray(50)
+        };
+        painter.circle(
+            positions[i],
+            NODE_RADIUS,
+            fill,
+            Stroke::new(1.5, Color32::from_gray(160)),
+        );
+        painter.text(
+            positions[i],
+            Align2::CENTER_CENTER,
+            short_name(state),
+            FontId::proportional(11.0),
+            if is_active {
+                Color32::BLACK
+            } else {
+                Color32::WHITE
+            },
+        );
+    }
+
+    if let Some(cur) = current {
+        if config.index_of(&cur.state).is_none() {
+            ui.colored_label(
+                Color32::from_rgb(255, 120, 0),
+                format!("State \"{}\" isn't in the config", cur.state),
+            );
+        } else if let Some(prev) = app.state_history.previous()
+            && config.index_of(&prev.state).is_some()
+            && !config.allows(&prev.state, &cur.state)
+        {
+            ui.colored_label(
+                Color32::from_rgb(255, 120, 0),
+                format!("Unexpected transition {} -> {}", prev.state, cur.state),
+            );
+        }
+    }
+
+    state_timeline(ui, app, now);
+}
+
+fn state_timeline(ui: &mut Ui, app: &FrcUi, now: f64) {
+    ui.label("Recent states:");
+    ScrollArea::vertical()
+        .id_salt("StateTimeline")
+        .max_height(150.0)
+        .show(ui, |ui| {
+            for change in app.state_history.changes.iter().rev() {
+                ui.horizontal(|ui| {
+                    let duration = format!("{:.1} s", change.duration(now));
+                    if change.left_at.is_none() {
+                        ui.colored_label(ACTIVE_COLOR, &change.state);
+                        ui.weak(format!("{} (current)", duration));
+                    } else {
+                        ui.label(&change.state);
+                        ui.weak(duration);
+                    }
+                });
+            }
+        });
+}
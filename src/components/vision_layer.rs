@@ -0,0 +1,178 @@
+use egui::{Align2, Color32, FontId, Painter, Rect, Stroke, Vec2};
+
+use crate::{
+    FrcUi,
+    components::field_view::{FIELD_LENGTH_M, FIELD_WIDTH_M, FieldTransform, robot_pose},
+    nt_paths,
+    nt_util::NTValueType,
+};
+
+// botpose layout: [x, y, z, roll, pitch, yaw, latency, tagCount, tagSpan, avgDist, avgArea]
+// followed by [id, txnc, tync, ta, distToCamera, distToRobot, ambiguity] per tag.
+const BOTPOSE_HEADER_LEN: usize = 11;
+const BOTPOSE_TAG_STRIDE: usize = 7;
+
+const VISION_COLOR: Color32 = Color32::from_rgb(255, 140, 0);
+const TAG_COLOR: Color32 = Color32::from_rgb(230, 230, 230);
+
+/// A MegaTag pose estimate, always converted to the blue alliance origin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisionEstimate {
+    pub x: f64,
+    pub y: f64,
+    pub yaw_deg: f64,
+    pub latency_ms: f64,
+    pub tag_ids: Vec<i64>,
+}
+
+/// Parses a limelight `botpose_wpiblue`/`botpose_wpired` array. Returns None when no tags are
+/// in view, since the limelight publishes an all-zero pose then.
+/// `field_size` is needed to move red origin poses over to the blue origin.
+pub fn parse_botpose(
+    arr: &[f64],
+    red_origin: bool,
+    field_size: (f64, f64),
+) -> Option<VisionEstimate> {
+    if arr.len() < BOTPOSE_HEADER_LEN || arr[7] < 1.0 {
+        return None;
+    }
+    let (mut x, mut y, mut yaw_deg) = (arr[0], arr[1], arr[5]);
+    if red_origin {
+        x = field_size.0 - x;
+        y = field_size.1 - y;
+        yaw_deg += 180.0;
+    }
+    let tag_ids = arr[BOTPOSE_HEADER_LEN..]
+        .chunks_exact(BOTPOSE_TAG_STRIDE)
+        .map(|tag| tag[0] as i64)
+        .collect();
+    Some(VisionEstimate {
+        x,
+        y,
+        yaw_deg,
+        latency_ms: arr[6],
+        tag_ids,
+    })
+}
+
+/// Gets the estimate from a limelight, preferring `botpose_wpiblue` since that's our field frame.
+pub fn limelight_estimate(app: &FrcUi, name: &str) -> Option<VisionEstimate> {
+    let field_size = app
+        .apriltag_layout
+        .as_ref()
+        .map(|l| (l.field.length, l.field.width))
+        .unwrap_or((FIELD_LENGTH_M, FIELD_WIDTH_M));
+    let get = |path: String| match app.listened_values.get(&path) {
+        Some(NTValueType::DoubleArray(arr)) => Some(arr.as_slice()),
+        _ => None,
+    };
+    get(nt_paths::limelight_botpose_blue(name))
+        .and_then(|arr| parse_botpose(arr, false, field_size))
+        .or_else(|| {
+            get(nt_paths::limelight_botpose_red(name))
+                .and_then(|arr| parse_botpose(arr, true, field_size))
+        })
+}
+
+pub fn draw_vision(painter: &Painter, transform: &FieldTransform, app: &FrcUi) {
+    let estimates: Vec<(&String, VisionEstimate)> = app
+        .limelight_names
+        .iter()
+        .filter_map(|name| limelight_estimate(app, name).map(|e| (name, e)))
+        .collect();
+
+    // Tags first so the poses draw on top.
+    if let Some(layout) = &app.apriltag_layout {
+        let size = 0.25 * transform.points_per_meter();
+        for tag in &layout.tags {
+            let visible = estimates.iter().any(|(_, e)| e.tag_ids.contains(&tag.id));
+            let pos = transform.to_screen(tag.pose.translation.x, tag.pose.translation.y);
+            let color = if visible {
+                TAG_COLOR
+            } else {
+                TAG_COLOR.gamma_multiply(0.3)
+            };
+            painter.rect_filled(
+                Rect::from_center_size(pos, Vec2::splat(size.max(6.0))),
+                1.0,
+                color,
+            );
+            // Little tick showing which way the tag faces.
+            painter.line_segment(
+                [
+                    pos,
+                    pos + transform.heading_to_screen(tag.pose.rotation.quaternion.yaw())
+                        * size.max(6.0),
+                ],
+                Stroke::new(2.0, color),
+            );
+            painter.text(
+                pos + Vec2::new(0.0, -size.max(6.0)),
+                Align2::CENTER_BOTTOM,
+                tag.id.to_string(),
+                FontId::proportional(11.0),
+                color,
+            );
+        }
+    }
+
+    let odometry = robot_pose(app);
+    for (name, estimate) in &estimates {
+        let pos = transform.to_screen(estimate.x, estimate.y);
+
+        if let Some(layout) = &app.apriltag_layout {
+            for id in &estimate.tag_ids {
+                if let Some(tag) = layout.tag(*id) {
+                    painter.line_segment(
+                        [
+                            pos,
+                            transform.to_screen(tag.pose.translation.x, tag.pose.translation.y),
+                        ],
+                        Stroke::new(1.0, VISION_COLOR.gamma_multiply(0.4)),
+                    );
+                }
+            }
+        }
+
+        // Odometry vs vision error.
+        if let Some((x, y, _)) = odometry {
+            let error = ((estimate.x - x).powi(2) + (estimate.y - y).powi(2)).sqrt();
+            let odom_pos = transform.to_screen(x, y);
+            painter.line_segment([odom_pos, pos], Stroke::new(2.0, error_color(error)));
+            painter.text(
+                odom_pos + (pos - odom_pos) / 2.0,
+                Align2::LEFT_BOTTOM,
+                format!("{:.2} m", error),
+                FontId::proportional(12.0),
+                error_color(error),
+            );
+        }
+
+        painter.circle_stroke(pos, 12.0, Stroke::new(2.5, VISION_COLOR));
+        painter.line_segment(
+            [
+                pos,
+                pos + transform.heading_to_screen(estimate.yaw_deg.to_radians()) * 16.0,
+            ],
+            Stroke::new(2.5, VISION_COLOR),
+        );
+        painter.text(
+            pos + Vec2::new(14.0, 14.0),
+            Align2::LEFT_TOP,
+            format!("{} ({:.0} ms)", name, estimate.latency_ms),
+            FontId::proportional(11.0),
+            VISION_COLOR,
+        );
+    }
+}
+
+/// Green when localization agrees with vision, red when it's drifted off.
+fn error_color(error_m: f64) -> Color32 {
+    if error_m < 0.15 {
+        Color32::from_rgb(50, 220, 50)
+    } else if error_m < 0.5 {
+        Color32::from_rgb(240, 220, 0)
+    } else {
+        Color32::from_rgb(255, 50, 50)
+    }
+}
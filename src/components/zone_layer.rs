@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use egui::{Align2, Color32, FontId, Mesh, Painter, Pos2, Shape, Stroke, Vec2};
+
+use crate::{
+    FrcUi,
+    components::field_view::{FieldTransform, robot_pose},
+    field_model::{Zone, ZoneKind},
+    nt_paths,
+    nt_util::NTValueType,
+};
+
+/// Tracks which zones the robot is in, so we only publish to NT when that changes.
+pub struct ZoneLayer {
+    pub show: bool,
+    inside: HashMap<String, bool>,
+}
+
+impl Default for ZoneLayer {
+    fn default() -> Self {
+        Self {
+            show: true,
+            inside: HashMap::new(),
+        }
+    }
+}
+
+impl ZoneLayer {
+    pub fn is_inside(&self, zone: &str) -> bool {
+        self.inside.get(zone).copied().unwrap_or(false)
+    }
+}
+
+pub fn zone_color(zone: &Zone) -> Color32 {
+    if let Some([r, g, b, a]) = zone.color {
+        return Color32::from_rgba_unmultiplied(r, g, b, a);
+    }
+    match zone.kind {
+        ZoneKind::Shooting => Color32::from_rgba_unmultiplied(255, 140, 0, 70),
+        ZoneKind::NoGo => Color32::from_rgba_unmultiplied(255, 0, 0, 70),
+        ZoneKind::Intake => Color32::from_rgba_unmultiplied(0, 200, 255, 70),
+        ZoneKind::Other => Color32::from_rgba_unmultiplied(200, 200, 200, 50),
+    }
+}
+
+/// Recomputes inside/outside for every zone and publishes the ones that changed.
+/// Runs every frame whether or not the field is drawn.
+pub fn update_zone_states(app: &mut FrcUi) {
+    let robot = robot_pose(app);
+    let mut publish = Vec::new();
+    for zone in &app.field_model.zones {
+        let inside = robot.is_some_and(|(x, y, _)| zone.contains(x, y));
+        let changed = app.zone_layer.inside.insert(zone.name.clone(), inside) != Some(inside);
+        if changed && zone.publish {
+            publish.push((nt_paths::zone_state(&zone.name), inside));
+        }
+    }
+    for (path, inside) in publish {
+        app.data_mut().publish(&path, NTValueType::Boolean(inside));
+    }
+}
+
+/// Color the robot marker should take, from the first zone (in file order) it's inside.
+pub fn robot_zone_color(app: &FrcUi) -> Option<Color32> {
+    app.field_model
+        .zones
+        .iter()
+        .find(|z| app.zone_layer.is_inside(&z.name))
+        .map(|z| zone_color(z).to_opaque())
+}
+
+pub fn draw_zones(painter: &Painter, transform: &FieldTransform, app: &FrcUi) {
+    for zone in &app.field_model.zones {
+        if zone.points.len() < 3 {
+            continue;
+        }
+        let color = zone_color(zone);
+        let points: Vec<Pos2> = zone
+            .points
+            .iter()
+            .map(|[x, y]| transform.to_screen(*x, *y))
+            .collect();
+
+        let mut mesh = Mesh::default();
+        for p in &points {
+            mesh.colored_vertex(*p, color);
+        }
+        for [a, b, c] in zone.triangulate() {
+            mesh.add_triangle(a as u32, b as u32, c as u32);
+        }
+        painter.add(Shape::mesh(mesh));
+
+        let inside = app.zone_layer.is_inside(&zone.name);
+        let outline = if inside { 3.0 } else { 1.0 };
+        painter.add(Shape::closed_line(
+            points.clone(),
+            Stroke::new(outline, color.to_opaque()),
+        ));
+
+        let center =
+            points.iter().fold(Vec2::ZERO, |acc, p| acc + p.to_vec2()) / points.len() as f32;
+        painter.text(
+            center.to_pos2(),
+            Align2::CENTER_CENTER,
+            &zone.name,
+            FontId::proportional(12.0),
+            Color32::WHITE.gamma_multiply(if inside { 1.0 } else { 0.6 }),
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    nt_events::ValueUpdate,
+    nt_util::{ListenedValues, NTValueType},
+};
+
+/// Where the dashboard gets its values from and sends them to: the live NT client, a replay, or a
+/// mock for tests. Panels go through this instead of calling ntcore themselves.
+pub trait DataSource {
+    /// Latest value at `path`, None if there isn't one.
+    fn get(&self, path: &str) -> Option<NTValueType>;
+
+    /// Names of the topics we know about under `prefix`.
+    fn topics(&self, prefix: &str) -> Vec<String>;
+
+    /// Make everything under `prefix` show up in `get` and `topics`.
+    /// NT only sends us topics something is subscribed to.
+    fn subscribe(&mut self, prefix: &str);
+
+    /// Start getting every update for `path` from `read_updates`, including its current value.
+    fn listen(&mut self, path: &str);
+
+    /// Undoes `listen`.
+    fn unlisten(&mut self, path: &str);
+
+    /// Same as `listen`, for every topic starting with `prefix` ("/" for everything).
+    fn listen_prefix(&mut self, prefix: &str);
+
+    fn stop_prefix(&mut self, prefix: &str);
+
+    /// Everything that changed on listened topics since the last call, oldest first.
+    fn read_updates(&mut self) -> Vec<ValueUpdate>;
+
+    fn publish(&mut self, path: &str, value: NTValueType);
+
+    /// Current time on the same clock as update timestamps, microseconds.
+    fn now_us(&self) -> i64;
+
+    fn is_connected(&self) -> bool;
+
+    /// Point the source at a robot. Only means something for live sources.
+    fn connect(&mut self, _team: u32, _port: u32) {}
+
+    fn get_double(&self, path: &str, default: f64) -> f64 {
+        match self.get(path) {
+            Some(NTValueType::Double(d)) => d,
+            _ => default,
+        }
+    }
+
+    fn get_integer(&self, path: &str, default: i64) -> i64 {
+        match self.get(path) {
+            Some(NTValueType::Integer(i)) => i as i64,
+            _ => default,
+        }
+    }
+
+    fn get_boolean(&self, path: &str, default: bool) -> bool {
+        match self.get(path) {
+            Some(NTValueType::Boolean(b)) => b,
+            _ => default,
+        }
+    }
+
+    fn get_string(&self, path: &str, default: &str) -> String {
+        match self.get(path) {
+            Some(NTValueType::String(s)) => s,
+            _ => default.to_string(),
+        }
+    }
+
+    /// Empty if the topic doesn't exist (yet), like the rest of the array getters.
+    fn get_double_array(&self, path: &str) -> Vec<f64> {
+        match self.get(path) {
+            Some(NTValueType::DoubleArray(arr)) => arr,
+            _ => Vec::new(),
+        }
+    }
+
+    fn get_boolean_array(&self, path: &str) -> Vec<bool> {
+        match self.get(path) {
+            Some(NTValueType::BooleanArray(arr)) => arr,
+            _ => Vec::new(),
+        }
+    }
+
+    fn get_integer_array(&self, path: &str) -> Vec<i32> {
+        match self.get(path) {
+            Some(NTValueType::IntegerArray(arr)) => arr,
+            _ => Vec::new(),
+        }
+    }
+
+    fn get_string_array(&self, path: &str) -> Vec<String> {
+        match self.get(path) {
+            Some(NTValueType::StringArray(arr)) => arr,
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// In-memory source for tests. Clones share the same values, so a test can keep one to play the
+/// robot (`set`) and check what the UI wrote (`published`) after giving the other to `FrcUi`.
+#[derive(Clone, Default)]
+pub struct MockSource {
+    state: Rc<RefCell<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    values: ListenedValues,
+    published: Vec<(String, NTValueType)>,
+    now_us: i64,
+    connected: bool,
+    listening: Vec<String>,
+    listening_prefixes: Vec<String>,
+    pending: Vec<ValueUpdate>,
+}
+
+impl MockState {
+    fn is_listening(&self, path: &str) -> bool {
+        self.listening.iter().any(|p| p == path)
+            || self
+                .listening_prefixes
+                .iter()
+                .any(|p| path.starts_with(p.as_str()))
+    }
+
+    fn queue(&mut self, path: &str, value: NTValueType) {
+        let timestamp_us = self.now_us;
+        self.pending.push(ValueUpdate {
+            path: path.to_string(),
+            value,
+            timestamp_us,
+        });
+    }
+}
+
+impl MockSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Like the robot changing a value.
+    pub fn set(&self, path: &str, value: NTValueType) {
+        let mut state = self.state.borrow_mut();
+        if state.is_listening(path) {
+            state.queue(path, value.clone());
+        }
+        state.values.insert(path.to_string(), value);
+    }
+
+    pub fn set_now_us(&self, now_us: i64) {
+        self.state.borrow_mut().now_us = now_us;
+    }
+
+    pub fn set_connected(&self, connected: bool) {
+        self.state.borrow_mut().connected = connected;
+    }
+
+    /// Everything the UI published, in order.
+    pub fn published(&self) -> Vec<(String, NTValueType)> {
+        self.state.borrow().published.clone()
+    }
+
+    /// Last value published to `path`.
+    pub fn last_published(&self, path: &str) -> Option<NTValueType> {
+        self.state
+            .borrow()
+            .published
+            .iter()
+            .rev()
+            .find(|(p, _)| p == path)
+            .map(|(_, v)| v.clone())
+    }
+}
+
+impl DataSource for MockSource {
+    fn get(&self, path: &str) -> Option<NTValueType> {
+        self.state.borrow().values.get(path).cloned()
+    }
+
+    fn topics(&self, prefix: &str) -> Vec<String> {
+        self.state
+            .borrow()
+            .values
+            .keys()
+            .filter(|k| k.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+
+    fn subscribe(&mut self, _prefix: &str) {}
+
+    fn listen(&mut self, path: &str) {
+        let mut state = self.state.borrow_mut();
+        if state.listening.iter().any(|p| p == path) {
+            return;
+        }
+        // Already covered by a prefix, so its current value went out then.
+        let covered = state.is_listening(path);
+        state.listening.push(path.to_string());
+        // Listeners start with the current value, like ntcore's NT_EVENT_IMMEDIATE.
+        if !covered && let Some(value) = state.values.get(path).cloned() {
+            state.queue(path, value);
+        }
+    }
+
+    fn unlisten(&mut self, path: &str) {
+        self.state.borrow_mut().listening.retain(|p| p != path);
+    }
+
+    fn listen_prefix(&mut self, prefix: &str) {
+        let topics = self.topics(prefix);
+        let mut state = self.state.borrow_mut();
+        if state.listening_prefixes.iter().any(|p| p == prefix) {
+            return;
+        }
+        let new: Vec<String> = topics
+            .into_iter()
+            .filter(|path| !state.is_listening(path))
+            .collect();
+        state.listening_prefixes.push(prefix.to_string());
+        for path in new {
+            if let Some(value) = state.values.get(&path).cloned() {
+                state.queue(&path, value);
+            }
+        }
+    }
+
+    fn stop_prefix(&mut self, prefix: &str) {
+        self.state
+            .borrow_mut()
+            .listening_prefixes
+            .retain(|p| p != prefix);
+    }
+
+    fn read_updates(&mut self) -> Vec<ValueUpdate> {
+        std::mem::take(&mut self.state.borrow_mut().pending)
+    }
+
+    fn publish(&mut self, path: &str, value: NTValueType) {
+        let mut state = self.state.borrow_mut();
+        state.published.push((path.to_string(), value.clone()));
+        state.values.insert(path.to_string(), value);
+    }
+
+    fn now_us(&self) -> i64 {
+        self.state.borrow().now_us
+    }
+
+    fn is_connected(&self) -> bool {
+        self.state.borrow().connected
+    }
+}
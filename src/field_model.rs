@@ -0,0 +1,120 @@
+use std::{fs, path::Path};
+
+use serde::Deserialize;
+
+/// User-editable description of the field: strategy zones and the like.
+/// Loaded from a JSON file so strategy can change it without a rebuild.
+#[derive(Debug, Default, Deserialize)]
+pub struct FieldModel {
+    #[serde(default)]
+    pub zones: Vec<Zone>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneKind {
+    Shooting,
+    NoGo,
+    Intake,
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Zone {
+    pub name: String,
+    pub kind: ZoneKind,
+    /// Polygon corners in field meters (blue origin), in order. Doesn't need to be convex.
+    pub points: Vec<[f64; 2]>,
+    /// RGBA, overrides the default color for the zone's kind.
+    #[serde(default)]
+    pub color: Option<[u8; 4]>,
+    /// Publish whether the robot is inside this zone back to NT.
+    #[serde(default)]
+    pub publish: bool,
+}
+
+impl FieldModel {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text)
+            .map_err(|e| format!("bad field model {}: {}", path.display(), e))
+    }
+}
+
+impl Zone {
+    /// Even-odd ray casting, works for concave polygons too.
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        let mut inside = false;
+        let n = self.points.len();
+        for i in 0..n {
+            let [xi, yi] = self.points[i];
+            let [xj, yj] = self.points[(i + n - 1) % n];
+            if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// Splits the polygon into triangles (ear clipping), as indices into `points`.
+    /// egui can only fill convex polygons by itself.
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        let pts = &self.points;
+        let mut remaining: Vec<usize> = (0..pts.len()).collect();
+        // Ear test below assumes counter-clockwise winding.
+        if signed_area(pts) < 0.0 {
+            remaining.reverse();
+        }
+
+        let mut triangles = Vec::new();
+        while remaining.len() > 3 {
+            let n = remaining.len();
+            let ear = (0..n).find(|&i| {
+                let (a, b, c) = (
+                    remaining[(i + n - 1) % n],
+                    remaining[i],
+                    remaining[(i + 1) % n],
+                );
+                cross(pts[a], pts[b], pts[c]) > 0.0
+                    && !remaining
+                        .iter()
+                        .filter(|&&p| p != a && p != b && p != c)
+                        .any(|&p| in_triangle(pts[p], pts[a], pts[b], pts[c]))
+            });
+            let Some(i) = ear else {
+                break; // Self-intersecting or degenerate, draw what we have.
+            };
+            triangles.push([
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            ]);
+            remaining.remove(i);
+        }
+        if remaining.len() == 3 {
+            triangles.push([remaining[0], remaining[1], remaining[2]]);
+        }
+        triangles
+    }
+}
+
+fn signed_area(pts: &[[f64; 2]]) -> f64 {
+    let n = pts.len();
+    (0..n)
+        .map(|i| {
+            let [x1, y1] = pts[i];
+            let [x2, y2] = pts[(i + 1) % n];
+            x1 * y2 - x2 * y1
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+fn cross(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+fn in_triangle(p: [f64; 2], a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> bool {
+    cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+}
@@ -0,0 +1,146 @@
+use crate::{FrcUi, nt_paths, nt_util::NTValueType};
+
+/// The DS control word WPILib publishes as `/FMSInfo/FMSControlData`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ControlWord {
+    pub enabled: bool,
+    pub autonomous: bool,
+    pub test: bool,
+    pub e_stop: bool,
+    pub fms_attached: bool,
+    pub ds_attached: bool,
+}
+
+impl ControlWord {
+    pub fn from_bits(bits: i64) -> Self {
+        Self {
+            enabled: bits & 0x01 != 0,
+            autonomous: bits & 0x02 != 0,
+            test: bits & 0x04 != 0,
+            e_stop: bits & 0x08 != 0,
+            fms_attached: bits & 0x10 != 0,
+            ds_attached: bits & 0x20 != 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchPhase {
+    Disabled,
+    Auto,
+    Teleop,
+    Endgame,
+    Test,
+    EStopped,
+}
+
+impl MatchPhase {
+    pub fn from_control_word(
+        word: ControlWord,
+        time_left: Option<f64>,
+        endgame_start_s: f64,
+    ) -> Self {
+        if word.e_stop {
+            Self::EStopped
+        } else if !word.enabled {
+            Self::Disabled
+        } else if word.test {
+            Self::Test
+        } else if word.autonomous {
+            Self::Auto
+        } else if time_left.is_some_and(|t| t <= endgame_start_s) {
+            Self::Endgame
+        } else {
+            Self::Teleop
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Disabled => "DISABLED",
+            Self::Auto => "AUTO",
+            Self::Teleop => "TELEOP",
+            Self::Endgame => "ENDGAME",
+            Self::Test => "TEST",
+            Self::EStopped => "E-STOPPED",
+        }
+    }
+}
+
+/// None until the robot has published a control word.
+pub fn control_word(app: &FrcUi) -> Option<ControlWord> {
+    match app.listened_values.get(nt_paths::FMS_CONTROL_DATA) {
+        Some(NTValueType::Integer(bits)) => Some(ControlWord::from_bits(*bits as i64)),
+        _ => None,
+    }
+}
+
+/// Everything WPILib publishes under `/FMSInfo`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FmsInfo {
+    pub event_name: String,
+    pub game_specific_message: String,
+    pub match_number: i32,
+    pub match_type: i32,
+    pub replay_number: i32,
+    pub station_number: i32,
+    pub is_red: bool,
+    pub control_word: Option<ControlWord>,
+}
+
+impl FmsInfo {
+    /// Short match label like "Q12" or "E3 (replay 1)". Empty when there's no match.
+    pub fn match_label(&self) -> String {
+        let prefix = match self.match_type {
+            1 => "P",
+            2 => "Q",
+            3 => "E",
+            _ => return String::new(),
+        };
+        if self.replay_number > 1 {
+            format!(
+                "{}{} (replay {})",
+                prefix, self.match_number, self.replay_number
+            )
+        } else {
+            format!("{}{}", prefix, self.match_number)
+        }
+    }
+
+    pub fn match_type_name(&self) -> &'static str {
+        match self.match_type {
+            1 => "Practice",
+            2 => "Qualification",
+            3 => "Elimination",
+            _ => "None",
+        }
+    }
+
+    pub fn on_field(&self) -> bool {
+        self.control_word.is_some_and(|w| w.fms_attached)
+    }
+}
+
+pub fn fms_info(app: &FrcUi) -> FmsInfo {
+    let string = |path: &str| match app.listened_values.get(path) {
+        Some(NTValueType::String(s)) => s.clone(),
+        _ => String::new(),
+    };
+    let int = |path: &str| match app.listened_values.get(path) {
+        Some(NTValueType::Integer(i)) => *i,
+        _ => 0,
+    };
+    FmsInfo {
+        event_name: string(nt_paths::FMS_EVENT_NAME),
+        game_specific_message: string(nt_paths::FMS_GAME_SPECIFIC_MESSAGE),
+        match_number: int(nt_paths::FMS_MATCH_NUMBER),
+        match_type: int(nt_paths::FMS_MATCH_TYPE),
+        replay_number: int(nt_paths::FMS_REPLAY_NUMBER),
+        station_number: int(nt_paths::FMS_STATION_NUMBER),
+        is_red: matches!(
+            app.listened_values.get(nt_paths::FMS_IS_RED_ALLIANCE),
+            Some(NTValueType::Boolean(true))
+        ),
+        control_word: control_word(app),
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    nt_paths,
+    nt_util::{ListenedValues, NTValueType},
+};
+
+/// The DS has 6 joystick ports.
+pub const PORT_COUNT: usize = 6;
+
+/// One joystick the way the Driver Station sees it.
+/// Axes are -1..1, buttons are numbered from 1 like in WPILib, POVs are degrees or -1.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct JoystickState {
+    pub name: String,
+    pub axes: Vec<f64>,
+    pub buttons: Vec<bool>,
+    pub povs: Vec<i32>,
+}
+
+impl JoystickState {
+    pub fn is_empty(&self) -> bool {
+        self.axes.is_empty() && self.buttons.is_empty() && self.povs.is_empty()
+    }
+
+    pub fn button(&self, number: usize) -> bool {
+        number >= 1 && self.buttons.get(number - 1).copied().unwrap_or(false)
+    }
+
+    pub fn axis(&self, index: usize) -> f64 {
+        self.axes.get(index).copied().unwrap_or(0.0)
+    }
+
+    pub fn pov(&self) -> i32 {
+        self.povs.first().copied().unwrap_or(-1)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum JoystickSource {
+    /// What the robot republishes from the DS.
+    #[default]
+    Robot,
+    /// Read straight off this laptop's gamepads.
+    Local,
+}
+
+pub fn robot_joystick(values: &ListenedValues, port: usize) -> JoystickState {
+    let mut state = JoystickState::default();
+    if let Some(NTValueType::DoubleArray(axes)) = values.get(&nt_paths::joystick_axes(port)) {
+        state.axes = axes.clone();
+    }
+    if let Some(NTValueType::BooleanArray(buttons)) = values.get(&nt_paths::joystick_buttons(port))
+    {
+        state.buttons = buttons.clone();
+    }
+    if let Some(NTValueType::IntegerArray(povs)) = values.get(&nt_paths::joystick_povs(port)) {
+        state.povs = povs.clone();
+    }
+    state
+}
+
+/// DS style POV angle from dpad buttons, 0 is up and it goes clockwise.
+#[cfg(feature = "local-gamepad")]
+fn pov_from_dpad(up: bool, right: bool, down: bool, left: bool) -> i32 {
+    let x = right as i32 - left as i32;
+    let y = up as i32 - down as i32;
+    match (x, y) {
+        (0, 1) => 0,
+        (1, 1) => 45,
+        (1, 0) => 90,
+        (1, -1) => 135,
+        (0, -1) => 180,
+        (-1, -1) => 225,
+        (-1, 0) => 270,
+        (-1, 1) => 315,
+        _ => -1,
+    }
+}
+
+pub struct Joysticks {
+    pub source: JoystickSource,
+    pub port: usize,
+    #[cfg(feature = "local-gamepad")]
+    gilrs: Option<gilrs::Gilrs>,
+    local: Vec<JoystickState>,
+}
+
+impl Default for Joysticks {
+    fn default() -> Self {
+        Self {
+            source: JoystickSource::Robot,
+            port: 0,
+            #[cfg(feature = "local-gamepad")]
+            gilrs: gilrs::Gilrs::new()
+                .inspect_err(|e| println!("Couldn't open local gamepads: {}", e))
+                .ok(),
+            local: Vec::new(),
+        }
+    }
+}
+
+impl Joysticks {
+    pub fn local_available() -> bool {
+        cfg!(feature = "local-gamepad")
+    }
+
+    pub fn state(&self, values: &ListenedValues) -> JoystickState {
+        match self.source {
+            JoystickSource::Robot => robot_joystick(values, self.port),
+            JoystickSource::Local => self.local.get(self.port).cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Call every frame. Does nothing without the local-gamepad feature.
+    pub fn update_local(&mut self) {
+        #[cfg(feature = "local-gamepad")]
+        if let Some(gilrs) = &mut self.gilrs {
+            use gilrs::{Axis, Button};
+
+            // Drain events so gilrs updates its cached state.
+            while gilrs.next_event().is_some() {}
+
+            // Same order the DS uses for an Xbox controller.
+            self.local = gilrs
+                .gamepads()
+                .take(PORT_COUNT)
+                .map(|(_, pad)| {
+                    let trigger =
+                        |b: Button| pad.button_data(b).map(|d| d.value() as f64).unwrap_or(0.0);
+                    JoystickState {
+                        name: pad.name().to_string(),
+                        axes: vec![
+                            pad.value(Axis::LeftStickX) as f64,
+                            -pad.value(Axis::LeftStickY) as f64,
+                            trigger(Button::LeftTrigger2),
+                            trigger(Button::RightTrigger2),
+                            pad.value(Axis::RightStickX) as f64,
+                            -pad.value(Axis::RightStickY) as f64,
+                        ],
+                        buttons: [
+                            Button::South,
+                            Button::East,
+                            Button::West,
+                            Button::North,
+                            Button::LeftTrigger,
+                            Button::RightTrigger,
+                            Button::Select,
+                            Button::Start,
+                            Button::LeftThumb,
+                            Button::RightThumb,
+                        ]
+                        .into_iter()
+                        .map(|b| pad.is_pressed(b))
+                        .collect(),
+                        povs: vec![pov_from_dpad(
+                            pad.is_pressed(Button::DPadUp),
+                            pad.is_pressed(Button::DPadRight),
+                            pad.is_pressed(Button::DPadDown),
+                            pad.is_pressed(Button::DPadLeft),
+                        )],
+                    }
+                })
+                .collect();
+        }
+    }
+}
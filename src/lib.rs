@@ -0,0 +1,557 @@
+use std::{collections::HashMap, path::Path, sync::Arc, thread};
+
+use egui::{Context, DragValue, Id, Modal};
+use mjpeg_rs::MJpeg;
+use opencv::videoio::{CAP_ANY, VideoCapture};
+
+use crate::{
+    apriltag_layout::AprilTagFieldLayout,
+    bindings::{Bindings, BindingsConfig},
+    components::{
+        alerts::{AlertTracker, alert_toasts, update_alerts},
+        chooser::{Choosers, poll_chooser},
+        field_3d::Field3dView,
+        field_view::FieldView,
+        game_piece_layer::GamePieceLayer,
+        graphs::{Graphs, graphs_window},
+        match_timer::{MatchTimer, match_timer_settings},
+        replay_bar::replay_bar,
+        zone_layer::{ZoneLayer, update_zone_states},
+    },
+    data_source::DataSource,
+    field_model::FieldModel,
+    joysticks::{Joysticks, PORT_COUNT},
+    match_logger::{LogTrigger, MatchLogger, update_logger},
+    nt_paths::LUNITE_COUNT,
+    nt_util::{ListenedValues, NTValueType},
+    replay::Replay,
+    sendables::{SMARTDASHBOARD_PREFIX, Sendables},
+    state_machine::{StateHistory, StateMachineConfig},
+};
+
+mod apriltag_layout;
+mod bindings;
+mod components;
+pub mod data_source;
+mod field_model;
+mod fms;
+mod joysticks;
+mod match_logger;
+#[cfg(feature = "rust-nt4")]
+pub mod nt4_client;
+pub mod nt_events;
+pub mod nt_instance;
+pub mod nt_paths;
+pub mod nt_util;
+mod replay;
+mod sendables;
+mod state_machine;
+pub mod wpilog;
+
+pub use components::field_view::FieldTransform;
+#[cfg(feature = "runtime-ntcore")]
+pub use components::ntcoreffi_missing::NtcoreffiMissing;
+
+pub struct FrcUi {
+    team_number: u32,
+    port: u32,
+    /// Live data, used whenever we aren't replaying.
+    source: Box<dyn DataSource>,
+    camera_ips: HashMap<String, String>,
+    camera_streams: HashMap<String, VideoCapture>,
+    settings_modal_open: bool,
+    listened_values: ListenedValues,
+    field_view: FieldView,
+    field_3d: Field3dView,
+    game_piece_layer: GamePieceLayer,
+    /// NT table names (hostnames) of the limelights to show pose estimates for.
+    limelight_names: Vec<String>,
+    apriltag_layout_path: String,
+    apriltag_layout: Option<AprilTagFieldLayout>,
+    field_model_path: String,
+    field_model: FieldModel,
+    zone_layer: ZoneLayer,
+    match_timer: MatchTimer,
+    state_machine_path: String,
+    state_machine: StateMachineConfig,
+    state_history: StateHistory,
+    bindings_path: String,
+    bindings: Bindings,
+    joysticks: Joysticks,
+    choosers: Choosers,
+    sendables: Sendables,
+    alerts: AlertTracker,
+    graphs: Graphs,
+    logger: MatchLogger,
+    /// Playing back a log, takes over from `source` while it's there.
+    replay: Option<Replay>,
+    replay_path: String,
+
+    m: Arc<MJpeg>,
+    tmp: usize,
+}
+
+impl FrcUi {
+    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+        #[cfg(not(feature = "rust-nt4"))]
+        let source = Box::new(crate::nt_instance::NtInstance::start_client("FRC_UI"));
+        #[cfg(feature = "rust-nt4")]
+        let source = Box::new(crate::nt4_client::Nt4Client::start("FRC_UI"));
+        let mut s = Self::with_source(source);
+
+        let m_c = s.m.clone();
+        thread::spawn(move || m_c.run("127.0.0.1:8081").unwrap());
+
+        s.try_reconnect();
+        s.update_cameras();
+
+        s
+    }
+
+    /// The dashboard reading from `source`, without connecting to anything else:
+    /// no MJPEG server, no camera streams and no robot address set. Tests use this with a MockSource.
+    pub fn with_source(mut source: Box<dyn DataSource>) -> Self {
+        let mut camera_ips = HashMap::new();
+        // Add cameras here
+        // camera_ips.insert(String::from("ll-front"), String::from("10.87.26.11:5800"));
+        // camera_ips.insert(String::from("ll-back"), String::from("10.87.26.12:5800"));
+        camera_ips.insert(
+            String::from("Intake Limelight"),
+            String::from("0.0.0.0:5800"),
+        );
+        camera_ips.insert(
+            String::from("Shooter Limelight"),
+            String::from("0.0.0.0:5800"),
+        );
+
+        let m = Arc::new(MJpeg::new());
+
+        // Sendables are found by looking through these topics.
+        source.subscribe(SMARTDASHBOARD_PREFIX);
+
+        // Start listening to needed values
+        let mut listened_values = HashMap::new();
+        // add_listener(&mut listened_values, nt_paths::GAME_TIME, nt);
+        // add_listener(&mut listened_values, nt_paths::CURRENT_STATE, nt);
+        // add_listener(&mut listened_values, nt_paths::KNOWN_LUNITE_POSITIONS, nt);
+        // add_listener(&mut listened_values, nt_paths::LUNITE_COUNT, nt);
+        // add_listener(&mut listened_values, nt_paths::ROBOT_2D_POSITION, nt);
+
+        let mut s = Self {
+            settings_modal_open: false,
+            team_number: 8726,
+            port: 5810,
+            camera_streams: HashMap::new(),
+            source,
+            camera_ips,
+            listened_values,
+            field_view: FieldView::default(),
+            field_3d: Field3dView::default(),
+            game_piece_layer: GamePieceLayer::default(),
+            limelight_names: vec![
+                String::from("limelight-intake"),
+                String::from("limelight-shooter"),
+            ],
+            apriltag_layout_path: String::from("apriltag_layout.json"),
+            apriltag_layout: None,
+            field_model_path: String::from("field_model.json"),
+            field_model: FieldModel::default(),
+            zone_layer: ZoneLayer::default(),
+            match_timer: MatchTimer::default(),
+            state_machine_path: String::from("state_machine.json"),
+            state_machine: StateMachineConfig::default(),
+            state_history: StateHistory::default(),
+            bindings_path: String::from("bindings.json"),
+            bindings: Bindings::default(),
+            joysticks: Joysticks::default(),
+            choosers: Choosers::default(),
+            sendables: Sendables::default(),
+            alerts: AlertTracker::default(),
+            graphs: Graphs::default(),
+            logger: MatchLogger::default(),
+            replay: None,
+            replay_path: String::new(),
+
+            m,
+            tmp: 0,
+        };
+
+        s.load_apriltag_layout();
+        s.load_field_model();
+        s.load_state_machine();
+        s.load_bindings();
+
+        s
+    }
+
+    fn load_bindings(&mut self) {
+        match BindingsConfig::load(Path::new(&self.bindings_path)) {
+            Ok(config) => self.bindings.from_file = config,
+            Err(e) => {
+                println!("Failed to load controller bindings: {}", e);
+                self.bindings.from_file = BindingsConfig::default();
+            }
+        }
+    }
+
+    fn load_state_machine(&mut self) {
+        match StateMachineConfig::load(Path::new(&self.state_machine_path)) {
+            Ok(config) => self.state_machine = config,
+            Err(e) => {
+                println!("Failed to load state machine config: {}", e);
+                self.state_machine = StateMachineConfig::default();
+            }
+        }
+    }
+
+    fn load_apriltag_layout(&mut self) {
+        match AprilTagFieldLayout::load(Path::new(&self.apriltag_layout_path)) {
+            Ok(layout) => self.apriltag_layout = Some(layout),
+            Err(e) => {
+                println!("Failed to load AprilTag layout: {}", e);
+                self.apriltag_layout = None;
+            }
+        }
+    }
+
+    fn load_field_model(&mut self) {
+        match FieldModel::load(Path::new(&self.field_model_path)) {
+            Ok(model) => self.field_model = model,
+            Err(e) => {
+                println!("Failed to load field model: {}", e);
+                self.field_model = FieldModel::default();
+            }
+        }
+    }
+
+    // connects to rio
+    fn try_reconnect(&mut self) {
+        self.source.connect(self.team_number, self.port);
+    }
+
+    // Sets up new camera streams based on the updated IP addresses.
+    fn update_cameras(&mut self) {
+        for (k, v) in &self.camera_ips {
+            if let Ok(cap) = VideoCapture::from_file(&format!("http://{}", v), CAP_ANY) {
+                // This seems to block, causing slow startup.
+                // TODO: Async this - and other camera operations too...
+                self.camera_streams.insert(k.clone(), cap);
+            } else {
+                println!("Failed to initialize camera {} with ip {}", k, v);
+            }
+        }
+    }
+
+    /// The replay while there is one, otherwise the live source.
+    fn data_mut(&mut self) -> &mut dyn DataSource {
+        match &mut self.replay {
+            Some(replay) => replay,
+            None => self.source.as_mut(),
+        }
+    }
+
+    /// Copies what the panels show out of the current source (the replay while there is one)
+    /// into `listened_values`, once per frame.
+    fn update_nt_values(&mut self) {
+        let source: &dyn DataSource = match &self.replay {
+            Some(replay) => replay,
+            None => self.source.as_ref(),
+        };
+        // gameTime. -1 when there's no match going on, which the timer shows as waiting.
+        let game_time = source.get_double(nt_paths::GAME_TIME, -1.0);
+        self.listened_values.insert(
+            nt_paths::GAME_TIME.to_string(),
+            NTValueType::Double(game_time),
+        );
+        let lunite_count = source.get_double(nt_paths::LUNITE_COUNT, -1.0);
+        if lunite_count != -1.0 {
+            self.listened_values.insert(
+                nt_paths::LUNITE_COUNT.to_string(),
+                NTValueType::Double(lunite_count),
+            );
+        }
+        let current_state = source.get_string(nt_paths::CURRENT_STATE, "Unknown");
+        self.listened_values.insert(
+            nt_paths::CURRENT_STATE.to_string(),
+            NTValueType::String(current_state),
+        );
+        let robot_pos = source.get_double_array(nt_paths::ROBOT_2D_POSITION);
+        if !robot_pos.is_empty() {
+            self.listened_values.insert(
+                nt_paths::ROBOT_2D_POSITION.to_string(),
+                NTValueType::DoubleArray(robot_pos),
+            );
+        }
+        // Empty lunite arrays are meaningful (nothing seen), so always overwrite these.
+        for path in [
+            nt_paths::KNOWN_LUNITE_POSITIONS,
+            nt_paths::KNOWN_LUNITE_METADATA,
+        ] {
+            self.listened_values.insert(
+                path.to_string(),
+                NTValueType::DoubleArray(source.get_double_array(path)),
+            );
+        }
+        let target_lunite = source.get_double(nt_paths::TARGET_LUNITE_ID, -1.0);
+        self.listened_values.insert(
+            nt_paths::TARGET_LUNITE_ID.to_string(),
+            NTValueType::Double(target_lunite),
+        );
+
+        // Robot published controller bindings, if any
+        self.bindings
+            .update_from_nt(&source.get_string(nt_paths::INPUT_BINDINGS, ""));
+
+        // DS joysticks
+        for port in 0..PORT_COUNT {
+            self.listened_values.insert(
+                nt_paths::joystick_axes(port),
+                NTValueType::DoubleArray(source.get_double_array(&nt_paths::joystick_axes(port))),
+            );
+            self.listened_values.insert(
+                nt_paths::joystick_buttons(port),
+                NTValueType::BooleanArray(
+                    source.get_boolean_array(&nt_paths::joystick_buttons(port)),
+                ),
+            );
+            self.listened_values.insert(
+                nt_paths::joystick_povs(port),
+                NTValueType::IntegerArray(source.get_integer_array(&nt_paths::joystick_povs(port))),
+            );
+        }
+
+        // Vision
+        for name in &self.limelight_names {
+            for path in [
+                nt_paths::limelight_botpose_blue(name),
+                nt_paths::limelight_botpose_red(name),
+            ] {
+                let arr = source.get_double_array(&path);
+                self.listened_values
+                    .insert(path, NTValueType::DoubleArray(arr));
+            }
+        }
+
+        // Auto chooser
+        poll_chooser(&mut self.listened_values, nt_paths::AUTO_CHOOSER, source);
+
+        // FMS
+        let is_red = source.get_boolean(nt_paths::FMS_IS_RED_ALLIANCE, false);
+        self.listened_values.insert(
+            nt_paths::FMS_IS_RED_ALLIANCE.to_string(),
+            NTValueType::Boolean(is_red),
+        );
+        let control_data = source.get_integer(nt_paths::FMS_CONTROL_DATA, -1);
+        if control_data != -1 {
+            self.listened_values.insert(
+                nt_paths::FMS_CONTROL_DATA.to_string(),
+                NTValueType::Integer(control_data as i32),
+            );
+        }
+        for path in [
+            nt_paths::FMS_EVENT_NAME,
+            nt_paths::FMS_GAME_SPECIFIC_MESSAGE,
+        ] {
+            self.listened_values.insert(
+                path.to_string(),
+                NTValueType::String(source.get_string(path, "")),
+            );
+        }
+        for path in [
+            nt_paths::FMS_MATCH_NUMBER,
+            nt_paths::FMS_MATCH_TYPE,
+            nt_paths::FMS_REPLAY_NUMBER,
+            nt_paths::FMS_STATION_NUMBER,
+        ] {
+            let value = source.get_integer(path, 0);
+            self.listened_values
+                .insert(path.to_string(), NTValueType::Integer(value as i32));
+        }
+    }
+}
+
+impl eframe::App for FrcUi {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.show(ctx);
+    }
+}
+
+impl FrcUi {
+    /// Reads new values and draws everything. This is the whole frame, split out of `update`
+    /// so tests can run it without an eframe window.
+    pub fn show(&mut self, ctx: &egui::Context) {
+        egui_extras::install_image_loaders(ctx);
+        ctx.include_bytes(
+            "bytes://bbots25-field.png",
+            include_bytes!("assets/bbots25-field.png"),
+        );
+        ctx.all_styles_mut(|style| {
+            style.override_font_id = Some(egui::FontId {
+                size: 20.0,
+                family: egui::FontFamily::Proportional,
+            });
+        });
+
+        let now = ctx.input(|i| i.time);
+        if let Some(replay) = &mut self.replay {
+            replay.advance(now);
+            if replay.rewound {
+                self.graphs.clear_samples();
+            }
+            // Nobody reads the live updates while replaying, don't let them pile up.
+            self.source.read_updates();
+        }
+        self.update_nt_values();
+
+        let source: &mut dyn DataSource = match &mut self.replay {
+            Some(replay) => replay,
+            None => self.source.as_mut(),
+        };
+        self.sendables
+            .update(&mut self.listened_values, source, now);
+        // Graphs get every sample from the event stream, not just one per frame.
+        let wanted: Vec<String> = self.graphs.paths().map(str::to_string).collect();
+        for old in &self.graphs.listening {
+            if !wanted.contains(old) {
+                source.unlisten(old);
+            }
+        }
+        for path in &wanted {
+            source.listen(path);
+        }
+        self.graphs.listening = wanted;
+        let updates = source.read_updates();
+        let nt_now_s = source.now_us() as f64 / 1e6;
+        self.graphs.record(&updates, nt_now_s);
+        // Logging a replay would just make a copy of it.
+        if self.replay.is_none() {
+            update_logger(self, &updates, now);
+        }
+        update_alerts(self, now);
+        update_zone_states(self);
+        // Not listened_values, that has "Unknown" when the robot hasn't said.
+        if let Some(NTValueType::String(state)) = self.data_mut().get(nt_paths::CURRENT_STATE) {
+            self.state_history.update(&state, ctx.input(|i| i.time));
+        }
+
+        components::fms_panel::fms_panel(ctx, self);
+
+        components::left_panel::left_panel(ctx, self);
+
+        components::bottom_panel::bottom_panel(ctx, self);
+
+        replay_bar(ctx, self);
+
+        components::right_panel::right_panel(ctx, self);
+
+        components::central_panel::central_panel(ctx, self);
+
+        graphs_window(ctx, self, nt_now_s);
+        alert_toasts(ctx, self);
+
+        if self.settings_modal_open {
+            let modal = Modal::new(Id::new("Settings Modal")).show(ctx, |ui| {
+                ui.vertical_centered_justified(|ui| {
+                    ui.heading("Connection Settings");
+                    ui.separator();
+                    ui.heading("Camera IP Addresses - include ports! e.x. 1.2.3.4:5800");
+                    for entry in &mut self.camera_ips {
+                        ui.horizontal(|ui| {
+                            ui.label(entry.0);
+                            ui.text_edit_singleline(entry.1);
+                        });
+                    }
+
+                    ui.heading("Limelight NT names (for pose estimates)");
+                    for name in &mut self.limelight_names {
+                        ui.text_edit_singleline(name);
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label("AprilTag layout JSON: ");
+                        ui.text_edit_singleline(&mut self.apriltag_layout_path);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Field model JSON: ");
+                        ui.text_edit_singleline(&mut self.field_model_path);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("State machine JSON: ");
+                        ui.text_edit_singleline(&mut self.state_machine_path);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Controller bindings JSON: ");
+                        ui.text_edit_singleline(&mut self.bindings_path);
+                    });
+
+                    ui.heading("Match timer");
+                    match_timer_settings(ui, &mut self.match_timer);
+
+                    ui.heading("Logging (.wpilog)");
+                    ui.checkbox(&mut self.logger.enabled, "Log NT data");
+                    ui.horizontal(|ui| {
+                        ui.label("When: ");
+                        egui::ComboBox::new("LogTrigger", "")
+                            .selected_text(self.logger.trigger.name())
+                            .show_ui(ui, |ui| {
+                                for trigger in LogTrigger::ALL {
+                                    ui.selectable_value(
+                                        &mut self.logger.trigger,
+                                        trigger,
+                                        trigger.name(),
+                                    );
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Folder: ");
+                        ui.text_edit_singleline(&mut self.logger.dir);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Topics (comma separated prefixes): ");
+                        ui.text_edit_singleline(&mut self.logger.prefixes);
+                    });
+
+                    ui.heading("RoboRIO connection settings");
+                    ui.horizontal(|ui| {
+                        ui.vertical_centered_justified(|ui| {
+                            ui.horizontal_centered(|ui| {
+                                ui.label("Team Number (0 for the simulator): ");
+                                ui.add(DragValue::new(&mut self.team_number).speed(1))
+                            });
+                            ui.horizontal_centered(|ui| {
+                                ui.label("Port: ");
+                                ui.add(DragValue::new(&mut self.port).speed(1))
+                            })
+                        });
+                    });
+
+                    ui.separator();
+                    ui.label("This will stall for a while while trying to connect to cameras!");
+                    ui.label("Don't worry, it didn't crash.");
+                    ui.label("Maybe I'll multithread this in the future to avoid this...");
+                    if ui.button("Save, Reconnect and Close").clicked() {
+                        self.try_reconnect();
+                        self.update_cameras();
+                        self.load_apriltag_layout();
+                        self.load_field_model();
+                        self.load_state_machine();
+                        self.load_bindings();
+                        self.settings_modal_open = false;
+                    }
+                });
+            });
+
+            if modal.should_close() {
+                self.try_reconnect();
+                self.update_cameras();
+                self.load_apriltag_layout();
+                self.load_field_model();
+                self.load_state_machine();
+                self.load_bindings();
+                self.settings_modal_open = false;
+            }
+        }
+
+        ctx.request_repaint(); // spam repaint just to be safe. lots of values change.
+    }
+}
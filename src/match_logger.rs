@@ -0,0 +1,210 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    FrcUi,
+    fms::{FmsInfo, control_word, fms_info},
+    nt_events::ValueUpdate,
+    wpilog::WpilogWriter,
+};
+
+/// Don't stop logging for short disables, like the gap between auto and teleop.
+const STOP_DELAY_S: f64 = 5.0;
+const FLUSH_INTERVAL_S: f64 = 1.0;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LogTrigger {
+    /// Log while the robot is enabled.
+    #[default]
+    Enabled,
+    /// Log the whole time we're connected.
+    Connected,
+}
+
+impl LogTrigger {
+    pub const ALL: [LogTrigger; 2] = [LogTrigger::Enabled, LogTrigger::Connected];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Enabled => "While enabled",
+            Self::Connected => "While connected",
+        }
+    }
+}
+
+struct OpenLog {
+    writer: WpilogWriter<BufWriter<File>>,
+    path: PathBuf,
+    last_flush: f64,
+}
+
+pub struct MatchLogger {
+    pub enabled: bool,
+    pub trigger: LogTrigger,
+    pub dir: String,
+    /// Comma separated topic prefixes, "/" logs everything.
+    pub prefixes: String,
+    log: Option<OpenLog>,
+    /// Prefixes we've asked the event stream for.
+    listening: Vec<String>,
+    /// When the trigger last said to stop, for STOP_DELAY_S.
+    stop_requested_at: Option<f64>,
+}
+
+impl Default for MatchLogger {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            trigger: LogTrigger::default(),
+            dir: String::from("logs"),
+            prefixes: String::from("/"),
+            log: None,
+            listening: Vec::new(),
+            stop_requested_at: None,
+        }
+    }
+}
+
+impl MatchLogger {
+    pub fn prefix_list(&self) -> Vec<String> {
+        self.prefixes
+            .split(',')
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect()
+    }
+
+    pub fn current_file(&self) -> Option<&Path> {
+        self.log.as_ref().map(|l| l.path.as_path())
+    }
+
+    fn wants(&self, path: &str) -> bool {
+        self.listening.iter().any(|p| path.starts_with(p.as_str()))
+    }
+}
+
+/// Like "2025Casj_Q12_1743200000.wpilog", or "practice_1743200000.wpilog" with no match.
+pub fn log_file_name(info: &FmsInfo, unix_s: u64) -> String {
+    let clean = |s: &str| -> String {
+        s.chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+            .collect()
+    };
+    let label = clean(&info.match_label());
+    let event = clean(&info.event_name);
+    match (event.is_empty(), label.is_empty()) {
+        (_, true) => format!("practice_{}.wpilog", unix_s),
+        (true, false) => format!("{}_{}.wpilog", label, unix_s),
+        (false, false) => format!("{}_{}_{}.wpilog", event, label, unix_s),
+    }
+}
+
+fn open_log(app: &FrcUi, now: f64) -> Result<OpenLog, String> {
+    let unix_s = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let dir = Path::new(&app.logger.dir);
+    fs::create_dir_all(dir).map_err(|e| format!("couldn't create {}: {}", dir.display(), e))?;
+    let path = dir.join(log_file_name(&fms_info(app), unix_s));
+    let mut writer = WpilogWriter::create(&path)
+        .map_err(|e| format!("couldn't create {}: {}", path.display(), e))?;
+
+    // Start with every topic's current value, since the event stream only sends changes and a
+    // topic that doesn't change during the match would never show up otherwise.
+    let nt_now = app.source.now_us();
+    for topic in app.source.topics("/") {
+        if app.logger.wants(&topic)
+            && let Some(value) = app.source.get(&topic)
+        {
+            writer
+                .append(&topic, &value, nt_now)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(OpenLog {
+        writer,
+        path,
+        last_flush: now,
+    })
+}
+
+fn close_log(mut log: OpenLog) {
+    match log.writer.flush() {
+        Ok(()) => println!("Saved log {}", log.path.display()),
+        Err(e) => println!("Failed to save log {}: {}", log.path.display(), e),
+    }
+}
+
+/// Call every frame with this frame's NT updates. `now` is egui time.
+pub fn update_logger(app: &mut FrcUi, updates: &[ValueUpdate], now: f64) {
+    // Keep the event stream listening to what we want to log.
+    let wanted = if app.logger.enabled {
+        app.logger.prefix_list()
+    } else {
+        Vec::new()
+    };
+    for old in &app.logger.listening {
+        if !wanted.contains(old) {
+            app.source.stop_prefix(old);
+        }
+    }
+    for prefix in &wanted {
+        app.source.listen_prefix(prefix);
+    }
+    app.logger.listening = wanted;
+
+    let should_log = app.logger.enabled
+        && match app.logger.trigger {
+            LogTrigger::Enabled => control_word(app).is_some_and(|w| w.enabled),
+            LogTrigger::Connected => app.source.is_connected(),
+        };
+    let should_stop = if should_log {
+        app.logger.stop_requested_at = None;
+        false
+    } else {
+        let since = *app.logger.stop_requested_at.get_or_insert(now);
+        !app.logger.enabled || now - since > STOP_DELAY_S
+    };
+
+    if should_log && app.logger.log.is_none() {
+        match open_log(app, now) {
+            Ok(log) => {
+                println!("Logging to {}", log.path.display());
+                app.logger.log = Some(log);
+            }
+            Err(e) => {
+                println!("Failed to start log: {}", e);
+                // Don't retry every frame.
+                app.logger.enabled = false;
+            }
+        }
+    }
+
+    if let Some(mut log) = app.logger.log.take() {
+        let mut result = Ok(());
+        for update in updates.iter().filter(|u| app.logger.wants(&u.path)) {
+            result = log
+                .writer
+                .append(&update.path, &update.value, update.timestamp_us);
+            if result.is_err() {
+                break;
+            }
+        }
+        if result.is_ok() && now - log.last_flush > FLUSH_INTERVAL_S {
+            log.last_flush = now;
+            result = log.writer.flush();
+        }
+        if let Err(e) = result {
+            println!("Failed writing log {}: {}", log.path.display(), e);
+        } else if should_stop {
+            close_log(log);
+        } else {
+            app.logger.log = Some(log);
+        }
+    }
+}
@@ -0,0 +1,971 @@
+//! NetworkTables 4 client in plain Rust, so the dashboard can run without ntcoreffi.
+//! Spec: https://github.com/wpilibsuite/allwpilib/blob/main/ntcore/doc/networktables4.adoc
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, ErrorKind},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde_json::json;
+use tungstenite::{
+    Message, WebSocket, client::IntoClientRequest, http::HeaderValue, protocol::CloseFrame,
+};
+
+use crate::{
+    data_source::DataSource,
+    nt_events::ValueUpdate,
+    nt_util::{ListenedValues, NTValueType},
+};
+
+const SUBPROTOCOLS: &str = "v4.1.networktables.first.wpi.edu, networktables.first.wpi.edu";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+const RETRY_PERIOD: Duration = Duration::from_millis(500);
+// How long a read waits before we go check for things to send.
+const POLL_PERIOD: Duration = Duration::from_millis(10);
+const TIME_SYNC_PERIOD: Duration = Duration::from_secs(3);
+// Topic id the server and client use for time sync pings.
+const TIME_SYNC_ID: i64 = -1;
+
+/// Our clock, microseconds since the first time anything asked.
+fn local_us() -> i64 {
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_micros() as i64
+}
+
+/// Type string and binary type id for a value, None for ones we can't send.
+fn nt_type(value: &NTValueType) -> Option<(&'static str, u8)> {
+    Some(match value {
+        NTValueType::Boolean(_) => ("boolean", 0),
+        NTValueType::Double(_) => ("double", 1),
+        NTValueType::Integer(_) => ("int", 2),
+        NTValueType::Float(_) => ("float", 3),
+        NTValueType::String(_) => ("string", 4),
+        NTValueType::BooleanArray(_) => ("boolean[]", 16),
+        NTValueType::DoubleArray(_) => ("double[]", 17),
+        NTValueType::IntegerArray(_) => ("int[]", 18),
+        NTValueType::FloatArray(_) => ("float[]", 19),
+        NTValueType::StringArray(_) => ("string[]", 20),
+        NTValueType::Unknown => return None,
+    })
+}
+
+fn to_msgpack(value: &NTValueType) -> rmpv::Value {
+    use rmpv::Value;
+    match value {
+        NTValueType::Unknown => Value::Nil,
+        NTValueType::Boolean(b) => Value::from(*b),
+        NTValueType::Double(d) => Value::from(*d),
+        NTValueType::Integer(i) => Value::from(*i as i64),
+        NTValueType::Float(f) => Value::from(*f),
+        NTValueType::String(s) => Value::from(s.as_str()),
+        NTValueType::BooleanArray(arr) => {
+            Value::Array(arr.iter().map(|b| Value::from(*b)).collect())
+        }
+        NTValueType::DoubleArray(arr) => {
+            Value::Array(arr.iter().map(|d| Value::from(*d)).collect())
+        }
+        NTValueType::IntegerArray(arr) => {
+            Value::Array(arr.iter().map(|i| Value::from(*i as i64)).collect())
+        }
+        NTValueType::FloatArray(arr) => Value::Array(arr.iter().map(|f| Value::from(*f)).collect()),
+        NTValueType::StringArray(arr) => {
+            Value::Array(arr.iter().map(|s| Value::from(s.as_str())).collect())
+        }
+    }
+}
+
+/// Reads a value by its binary type id. None for types we don't show (raw, msgpack...).
+fn from_msgpack(type_id: u64, value: &rmpv::Value) -> Option<NTValueType> {
+    // Doubles can come through as ints from other clients.
+    fn number(v: &rmpv::Value) -> Option<f64> {
+        v.as_f64().or_else(|| v.as_i64().map(|i| i as f64))
+    }
+    fn array<T>(v: &rmpv::Value, f: impl Fn(&rmpv::Value) -> Option<T>) -> Option<Vec<T>> {
+        v.as_array()?.iter().map(f).collect()
+    }
+    Some(match type_id {
+        0 => NTValueType::Boolean(value.as_bool()?),
+        1 => NTValueType::Double(number(value)?),
+        2 => NTValueType::Integer(value.as_i64()? as i32),
+        3 => NTValueType::Float(number(value)? as f32),
+        4 => NTValueType::String(value.as_str()?.to_string()),
+        16 => NTValueType::BooleanArray(array(value, rmpv::Value::as_bool)?),
+        17 => NTValueType::DoubleArray(array(value, number)?),
+        18 => NTValueType::IntegerArray(array(value, |v| v.as_i64().map(|i| i as i32))?),
+        19 => NTValueType::FloatArray(array(value, |v| number(v).map(|f| f as f32))?),
+        20 => NTValueType::StringArray(array(value, |v| v.as_str().map(str::to_string))?),
+        _ => return None,
+    })
+}
+
+/// Where to look for the robot, like ntcore's NT_SetServerTeam. Team 0 is this computer.
+fn team_servers(team: u32) -> Vec<String> {
+    if team == 0 {
+        return vec![String::from("localhost")];
+    }
+    vec![
+        format!("10.{}.{}.2", team / 100, team % 100),
+        format!("roborio-{}-frc.local", team),
+        String::from("172.22.11.2"),
+    ]
+}
+
+/// Client names go in the URL, so anything but letters and digits gets %-escaped.
+fn url_name(name: &str) -> String {
+    name.bytes()
+        .map(|b| match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+struct Subscription {
+    uid: i64,
+    topic: String,
+    prefix: bool,
+    /// Every value instead of the latest each period, for listeners.
+    all: bool,
+}
+
+impl Subscription {
+    fn covers(&self, path: &str) -> bool {
+        if self.prefix {
+            path.starts_with(self.topic.as_str())
+        } else {
+            path == self.topic
+        }
+    }
+
+    fn message(&self) -> serde_json::Value {
+        json!({
+            "method": "subscribe",
+            "params": {
+                "topics": [self.topic],
+                "subuid": self.uid,
+                "options": { "prefix": self.prefix, "all": self.all },
+            }
+        })
+    }
+}
+
+struct Publisher {
+    uid: i64,
+    type_str: &'static str,
+    type_id: u8,
+}
+
+impl Publisher {
+    fn message(&self, name: &str) -> serde_json::Value {
+        json!({
+            "method": "publish",
+            "params": { "name": name, "pubuid": self.uid, "type": self.type_str, "properties": {} }
+        })
+    }
+}
+
+enum Outgoing {
+    Text(serde_json::Value),
+    Value {
+        pubuid: i64,
+        type_id: u8,
+        value: rmpv::Value,
+    },
+}
+
+/// Everything the connection thread and the client share.
+#[derive(Default)]
+struct Shared {
+    servers: Vec<String>,
+    port: u32,
+    /// Bumped when the server changes, so the connection thread drops the old one.
+    generation: u64,
+    stop: bool,
+    connected: bool,
+    /// Server time minus `local_us`.
+    offset_us: i64,
+    /// Announced topics by id, for the current connection.
+    topics: HashMap<i64, String>,
+    values: ListenedValues,
+    subscriptions: Vec<Subscription>,
+    publishers: HashMap<String, Publisher>,
+    next_uid: i64,
+    listening: HashSet<String>,
+    listening_prefixes: Vec<String>,
+    pending: Vec<ValueUpdate>,
+    /// Waiting for the connection thread to send. Thrown out on reconnect, since everything in
+    /// here gets sent again from the subscriptions and publishers anyway.
+    outgoing: Vec<Outgoing>,
+}
+
+impl Shared {
+    fn now_us(&self) -> i64 {
+        local_us() + self.offset_us
+    }
+
+    fn uid(&mut self) -> i64 {
+        self.next_uid += 1;
+        self.next_uid
+    }
+
+    fn is_listening(&self, path: &str) -> bool {
+        self.listening.contains(path)
+            || self
+                .listening_prefixes
+                .iter()
+                .any(|p| path.starts_with(p.as_str()))
+    }
+
+    fn subscribe(&mut self, topic: &str, prefix: bool, all: bool) {
+        if self
+            .subscriptions
+            .iter()
+            .any(|s| s.topic == topic && s.prefix == prefix && (s.all || !all))
+        {
+            return;
+        }
+        let subscription = Subscription {
+            uid: self.uid(),
+            topic: topic.to_string(),
+            prefix,
+            all,
+        };
+        self.outgoing.push(Outgoing::Text(subscription.message()));
+        self.subscriptions.push(subscription);
+    }
+
+    /// Drops the subscription `subscribe` made with the same arguments, if there is one.
+    fn unsubscribe(&mut self, topic: &str, prefix: bool, all: bool) {
+        let Some(i) = self
+            .subscriptions
+            .iter()
+            .position(|s| s.topic == topic && s.prefix == prefix && s.all == all)
+        else {
+            return;
+        };
+        let subscription = self.subscriptions.remove(i);
+        let message = json!({ "method": "unsubscribe", "params": { "subuid": subscription.uid } });
+        self.outgoing.push(Outgoing::Text(message));
+    }
+
+    /// Subscribes to `path` if nothing covers it yet, like ntcore does when you get an entry.
+    fn ensure_subscribed(&mut self, path: &str) {
+        if !self.subscriptions.iter().any(|s| s.covers(path)) {
+            self.subscribe(path, false, false);
+        }
+    }
+
+    fn set_value(&mut self, path: &str, value: NTValueType, timestamp_us: i64) {
+        if self.is_listening(path) {
+            self.pending.push(ValueUpdate {
+                path: path.to_string(),
+                value: value.clone(),
+                timestamp_us,
+            });
+        }
+        self.values.insert(path.to_string(), value);
+    }
+
+    /// Announce what we have again on a new connection.
+    fn resend_all(&mut self) {
+        self.outgoing.clear();
+        let mut outgoing: Vec<Outgoing> = self
+            .subscriptions
+            .iter()
+            .map(|s| Outgoing::Text(s.message()))
+            .collect();
+        for (name, publisher) in &self.publishers {
+            outgoing.push(Outgoing::Text(publisher.message(name)));
+            if let Some(value) = self.values.get(name) {
+                outgoing.push(Outgoing::Value {
+                    pubuid: publisher.uid,
+                    type_id: publisher.type_id,
+                    value: to_msgpack(value),
+                });
+            }
+        }
+        self.outgoing = outgoing;
+    }
+
+    fn handle_text(&mut self, text: &str) {
+        let Ok(serde_json::Value::Array(messages)) = serde_json::from_str(text) else {
+            println!("NT4: bad text message from server: {}", text);
+            return;
+        };
+        for message in messages {
+            let params = &message["params"];
+            match message["method"].as_str() {
+                Some("announce") => {
+                    if let (Some(id), Some(name)) = (params["id"].as_i64(), params["name"].as_str())
+                    {
+                        self.topics.insert(id, name.to_string());
+                    }
+                }
+                Some("unannounce") => {
+                    if let Some(id) = params["id"].as_i64() {
+                        self.topics.remove(&id);
+                    }
+                }
+                // Properties don't matter to us.
+                _ => {}
+            }
+        }
+    }
+
+    fn handle_binary(&mut self, data: &[u8]) {
+        let mut rest = data;
+        while !rest.is_empty() {
+            let Ok(rmpv::Value::Array(message)) = rmpv::decode::read_value(&mut rest) else {
+                println!("NT4: bad binary message from server");
+                return;
+            };
+            let [id, timestamp, type_id, value] = message.as_slice() else {
+                continue;
+            };
+            let (Some(id), Some(timestamp), Some(type_id)) =
+                (id.as_i64(), timestamp.as_i64(), type_id.as_u64())
+            else {
+                continue;
+            };
+            if id == TIME_SYNC_ID {
+                // The server sends back the time we sent along with its own.
+                if let Some(sent_us) = value.as_i64() {
+                    let now = local_us();
+                    let half_rtt = (now - sent_us) / 2;
+                    self.offset_us = timestamp + half_rtt - now;
+                }
+                continue;
+            }
+            let Some(name) = self.topics.get(&id).cloned() else {
+                continue;
+            };
+            if let Some(value) = from_msgpack(type_id, value) {
+                self.set_value(&name, value, timestamp);
+            }
+        }
+    }
+}
+
+fn lock(shared: &Mutex<Shared>) -> MutexGuard<'_, Shared> {
+    // A panic on the other side shouldn't take the dashboard down with it.
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn open(server: &str, port: u32, name: &str) -> Result<WebSocket<TcpStream>, String> {
+    let addr = (server, port as u16)
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("{} has no address", server))?;
+    let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).map_err(|e| e.to_string())?;
+    stream.set_nodelay(true).map_err(|e| e.to_string())?;
+    let mut request = format!("ws://{}:{}/nt/{}", server, port, url_name(name))
+        .into_client_request()
+        .map_err(|e| e.to_string())?;
+    request.headers_mut().insert(
+        "Sec-WebSocket-Protocol",
+        HeaderValue::from_static(SUBPROTOCOLS),
+    );
+    let (socket, _) = tungstenite::client(request, stream).map_err(|e| e.to_string())?;
+    // Short reads from here on, so the loop gets to send things.
+    socket
+        .get_ref()
+        .set_read_timeout(Some(POLL_PERIOD))
+        .map_err(|e| e.to_string())?;
+    Ok(socket)
+}
+
+fn send(
+    socket: &mut WebSocket<TcpStream>,
+    message: Outgoing,
+    now_us: i64,
+) -> tungstenite::Result<()> {
+    match message {
+        Outgoing::Text(message) => socket.send(Message::text(json!([message]).to_string())),
+        Outgoing::Value {
+            pubuid,
+            type_id,
+            value,
+        } => {
+            let mut data = Vec::new();
+            let message =
+                rmpv::Value::Array(vec![pubuid.into(), now_us.into(), type_id.into(), value]);
+            rmpv::encode::write_value(&mut data, &message).map_err(io::Error::from)?;
+            socket.send(Message::binary(data))
+        }
+    }
+}
+
+fn time_sync(socket: &mut WebSocket<TcpStream>) -> tungstenite::Result<()> {
+    let mut data = Vec::new();
+    let message = rmpv::Value::Array(vec![
+        TIME_SYNC_ID.into(),
+        0.into(),
+        2.into(),
+        local_us().into(),
+    ]);
+    rmpv::encode::write_value(&mut data, &message).map_err(io::Error::from)?;
+    socket.send(Message::binary(data))
+}
+
+/// Talks to one server until the connection drops, the server changes or the client goes away.
+fn session(shared: &Mutex<Shared>, mut socket: WebSocket<TcpStream>, generation: u64) {
+    {
+        let mut s = lock(shared);
+        s.connected = true;
+        s.resend_all();
+    }
+    let mut last_sync: Option<Instant> = None;
+    let result = (|| -> tungstenite::Result<()> {
+        loop {
+            if last_sync.is_none_or(|t| t.elapsed() > TIME_SYNC_PERIOD) {
+                time_sync(&mut socket)?;
+                last_sync = Some(Instant::now());
+            }
+            let (outgoing, now_us) = {
+                let mut s = lock(shared);
+                if s.stop || s.generation != generation {
+                    return socket.close(None::<CloseFrame>);
+                }
+                (std::mem::take(&mut s.outgoing), s.now_us())
+            };
+            for message in outgoing {
+                send(&mut socket, message, now_us)?;
+            }
+
+            match socket.read() {
+                Ok(Message::Text(text)) => lock(shared).handle_text(&text),
+                Ok(Message::Binary(data)) => lock(shared).handle_binary(&data),
+                Ok(Message::Close(_)) => return Ok(()),
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(e))
+                    if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+                Err(e) => return Err(e),
+            }
+        }
+    })();
+    if let Err(e) = result {
+        println!("NT4: lost connection: {}", e);
+    }
+
+    let mut s = lock(shared);
+    s.connected = false;
+    // Ids only mean something on the connection that announced them.
+    s.topics.clear();
+}
+
+fn run(shared: Arc<Mutex<Shared>>, name: String) {
+    let mut attempt = 0;
+    loop {
+        let (servers, port, generation) = {
+            let s = lock(&shared);
+            if s.stop {
+                return;
+            }
+            (s.servers.clone(), s.port, s.generation)
+        };
+        if servers.is_empty() {
+            thread::sleep(RETRY_PERIOD);
+            continue;
+        }
+        let server = &servers[attempt % servers.len()];
+        attempt += 1;
+        match open(server, port, &name) {
+            Ok(socket) => {
+                println!("NT4: connected to {}:{}", server, port);
+                session(&shared, socket, generation);
+            }
+            // Not worth printing, this happens the whole time the robot is off.
+            Err(_) => thread::sleep(RETRY_PERIOD),
+        }
+    }
+}
+
+/// NT4 client without ntcoreffi. A thread keeps the connection to the server going (and retries
+/// when it drops), everything else just reads and writes shared state.
+pub struct Nt4Client {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl Nt4Client {
+    /// Starts a client called `name`. Call `connect` or `connect_to` to point it somewhere.
+    pub fn start(name: &str) -> Self {
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let thread_shared = shared.clone();
+        let name = name.to_string();
+        thread::spawn(move || run(thread_shared, name));
+        Self { shared }
+    }
+
+    /// Connect to a server by address instead of team number.
+    pub fn connect_to(&mut self, address: &str, port: u32) {
+        self.set_servers(vec![address.to_string()], port);
+    }
+
+    fn set_servers(&mut self, servers: Vec<String>, port: u32) {
+        let mut s = lock(&self.shared);
+        if s.servers == servers && s.port == port {
+            return;
+        }
+        s.servers = servers;
+        s.port = port;
+        s.generation += 1;
+    }
+}
+
+impl Drop for Nt4Client {
+    fn drop(&mut self) {
+        lock(&self.shared).stop = true;
+    }
+}
+
+impl DataSource for Nt4Client {
+    fn get(&self, path: &str) -> Option<NTValueType> {
+        let mut s = lock(&self.shared);
+        s.ensure_subscribed(path);
+        s.values.get(path).cloned()
+    }
+
+    fn topics(&self, prefix: &str) -> Vec<String> {
+        let s = lock(&self.shared);
+        let mut names: Vec<String> = s
+            .topics
+            .values()
+            .filter(|name| name.starts_with(prefix))
+            .cloned()
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn subscribe(&mut self, prefix: &str) {
+        lock(&self.shared).subscribe(prefix, true, false);
+    }
+
+    fn listen(&mut self, path: &str) {
+        let mut s = lock(&self.shared);
+        // Already covered by a prefix, so its current value went out then.
+        let covered = s.is_listening(path);
+        if !s.listening.insert(path.to_string()) {
+            return;
+        }
+        s.subscribe(path, false, true);
+        // Starts with the current value, like ntcore's NT_EVENT_IMMEDIATE.
+        if !covered && let Some(value) = s.values.get(path).cloned() {
+            let now = s.now_us();
+            s.set_value(path, value, now);
+        }
+    }
+
+    fn unlisten(&mut self, path: &str) {
+        let mut s = lock(&self.shared);
+        if s.listening.remove(path) {
+            s.unsubscribe(path, false, true);
+        }
+    }
+
+    fn listen_prefix(&mut self, prefix: &str) {
+        let mut s = lock(&self.shared);
+        if s.listening_prefixes.iter().any(|p| p == prefix) {
+            return;
+        }
+        let now = s.now_us();
+        let current: Vec<(String, NTValueType)> = s
+            .values
+            .iter()
+            .filter(|(path, _)| path.starts_with(prefix) && !s.is_listening(path))
+            .map(|(path, value)| (path.clone(), value.clone()))
+            .collect();
+        s.listening_prefixes.push(prefix.to_string());
+        s.subscribe(prefix, true, true);
+        for (path, value) in current {
+            s.pending.push(ValueUpdate {
+                path,
+                value,
+                timestamp_us: now,
+            });
+        }
+    }
+
+    fn stop_prefix(&mut self, prefix: &str) {
+        let mut s = lock(&self.shared);
+        s.listening_prefixes.retain(|p| p != prefix);
+        // Otherwise the server keeps sending every change for nobody.
+        s.unsubscribe(prefix, true, true);
+    }
+
+    fn read_updates(&mut self) -> Vec<ValueUpdate> {
+        std::mem::take(&mut lock(&self.shared).pending)
+    }
+
+    fn publish(&mut self, path: &str, value: NTValueType) {
+        let Some((type_str, type_id)) = nt_type(&value) else {
+            return;
+        };
+        let mut s = lock(&self.shared);
+        let pubuid = match s.publishers.get(path) {
+            Some(publisher) if publisher.type_str == type_str => publisher.uid,
+            existing => {
+                // New topic, or the type changed and the server won't take it on the old one.
+                if let Some(old) = existing {
+                    let message = json!({ "method": "unpublish", "params": { "pubuid": old.uid } });
+                    s.outgoing.push(Outgoing::Text(message));
+                }
+                let publisher = Publisher {
+                    uid: s.uid(),
+                    type_str,
+                    type_id,
+                };
+                s.outgoing.push(Outgoing::Text(publisher.message(path)));
+                let uid = publisher.uid;
+                s.publishers.insert(path.to_string(), publisher);
+                uid
+            }
+        };
+        s.outgoing.push(Outgoing::Value {
+            pubuid,
+            type_id,
+            value: to_msgpack(&value),
+        });
+        let now = s.now_us();
+        s.set_value(path, value, now);
+    }
+
+    fn now_us(&self) -> i64 {
+        lock(&self.shared).now_us()
+    }
+
+    fn is_connected(&self) -> bool {
+        lock(&self.shared).connected
+    }
+
+    // connects to rio, or to the simulator on this computer for team 0
+    fn connect(&mut self, team: u32, port: u32) {
+        self.set_servers(team_servers(team), port);
+    }
+}
+
+// Shared with the integration tests.
+#[cfg(test)]
+#[path = "../tests/common/fixtures.rs"]
+mod fixtures;
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use tungstenite::handshake::server::{Request, Response};
+
+    use super::fixtures::{TIMEOUT, every_type, wait_for};
+    use super::*;
+
+    #[test]
+    fn numbers_come_in_as_the_topic_type() {
+        // Other clients are allowed to send whole doubles as ints.
+        assert_eq!(
+            from_msgpack(1, &rmpv::Value::from(2)),
+            Some(NTValueType::Double(2.0))
+        );
+        assert_eq!(
+            from_msgpack(19, &rmpv::Value::Array(vec![1.into(), 0.5.into()])),
+            Some(NTValueType::FloatArray(vec![1.0, 0.5]))
+        );
+        // Raw bytes aren't something we show.
+        assert_eq!(from_msgpack(5, &rmpv::Value::Binary(vec![1, 2])), None);
+    }
+
+    #[test]
+    fn team_numbers_become_robot_addresses() {
+        assert_eq!(
+            team_servers(4788),
+            ["10.47.88.2", "roborio-4788-frc.local", "172.22.11.2"]
+        );
+        assert_eq!(team_servers(254)[0], "10.2.54.2");
+        assert_eq!(team_servers(0), ["localhost"]);
+    }
+
+    /// Plays the server side of NT4 for one test, one connection at a time.
+    struct MockServer {
+        listener: TcpListener,
+        socket: Option<WebSocket<TcpStream>>,
+    }
+
+    impl MockServer {
+        fn start() -> Self {
+            Self {
+                listener: TcpListener::bind("127.0.0.1:0").unwrap(),
+                socket: None,
+            }
+        }
+
+        fn port(&self) -> u32 {
+            self.listener.local_addr().unwrap().port() as u32
+        }
+
+        fn accept(&mut self) {
+            let (stream, _) = self.listener.accept().unwrap();
+            stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+            // Clients hang up on servers that don't pick one of their subprotocols.
+            let callback = |request: &Request, mut response: Response| {
+                assert_eq!(request.uri().path(), "/nt/test%20client");
+                response.headers_mut().insert(
+                    "Sec-WebSocket-Protocol",
+                    HeaderValue::from_static("v4.1.networktables.first.wpi.edu"),
+                );
+                Ok(response)
+            };
+            self.socket = Some(tungstenite::accept_hdr(stream, callback).unwrap());
+        }
+
+        fn socket(&mut self) -> &mut WebSocket<TcpStream> {
+            self.socket.as_mut().expect("nothing connected")
+        }
+
+        /// Next text message with `method`, skipping everything else.
+        fn expect_text(&mut self, method: &str) -> serde_json::Value {
+            loop {
+                if let Message::Text(text) = self.socket().read().unwrap() {
+                    let messages: Vec<serde_json::Value> = serde_json::from_str(&text).unwrap();
+                    if let Some(m) = messages.into_iter().find(|m| m["method"] == method) {
+                        return m["params"].clone();
+                    }
+                }
+            }
+        }
+
+        /// Next binary message whose topic id is `id`, as [id, timestamp, type, value].
+        fn expect_binary(&mut self, id: i64) -> Vec<rmpv::Value> {
+            loop {
+                if let Message::Binary(data) = self.socket().read().unwrap() {
+                    let mut rest = &data[..];
+                    while !rest.is_empty() {
+                        let value = rmpv::decode::read_value(&mut rest).unwrap();
+                        let message = value.as_array().unwrap().clone();
+                        if message[0].as_i64() == Some(id) {
+                            return message;
+                        }
+                    }
+                }
+            }
+        }
+
+        fn announce(&mut self, id: i64, name: &str, type_str: &str) {
+            let message = json!([{
+                "method": "announce",
+                "params": { "name": name, "id": id, "type": type_str, "properties": {} }
+            }]);
+            self.socket()
+                .send(Message::text(message.to_string()))
+                .unwrap();
+        }
+
+        /// Sends all of `values` in one frame, like the server does each update period.
+        fn send_values(&mut self, id: i64, type_id: u8, values: &[rmpv::Value]) {
+            let mut data = Vec::new();
+            for (i, value) in values.iter().enumerate() {
+                let message = rmpv::Value::Array(vec![
+                    id.into(),
+                    (1000 + i as i64).into(),
+                    type_id.into(),
+                    value.clone(),
+                ]);
+                rmpv::encode::write_value(&mut data, &message).unwrap();
+            }
+            self.socket().send(Message::binary(data)).unwrap();
+        }
+    }
+
+    fn connect() -> (MockServer, Nt4Client) {
+        let mut server = MockServer::start();
+        let mut client = Nt4Client::start("test client");
+        client.connect_to("127.0.0.1", server.port());
+        server.accept();
+        wait_for("client to connect", || client.is_connected());
+        (server, client)
+    }
+
+    /// Everything the client publishes comes back under /echo/ from the server, so every type goes
+    /// through both our encoding and decoding.
+    #[test]
+    fn every_type_survives_the_trip() {
+        let (mut server, mut client) = connect();
+        client.subscribe("/echo/");
+        server.expect_text("subscribe");
+        for (id, (path, value)) in every_type().into_iter().enumerate() {
+            client.publish(path, value.clone());
+            let params = server.expect_text("publish");
+            let message = server.expect_binary(params["pubuid"].as_i64().unwrap());
+            let echo = format!("/echo{}", path);
+            server.announce(id as i64, &echo, params["type"].as_str().unwrap());
+            let type_id = message[2].as_u64().unwrap() as u8;
+            server.send_values(id as i64, type_id, &[message[3].clone()]);
+            wait_for(&echo, || client.get(&echo).as_ref() == Some(&value));
+        }
+    }
+
+    #[test]
+    fn announced_values_show_up() {
+        let (mut server, client) = connect();
+        assert_eq!(client.get("/robot/speed"), None);
+
+        let params = server.expect_text("subscribe");
+        assert_eq!(params["topics"], json!(["/robot/speed"]));
+        assert_eq!(params["options"]["prefix"], false);
+        server.announce(7, "/robot/speed", "double");
+        server.send_values(7, 1, &[2.5.into()]);
+        wait_for("value", || {
+            client.get("/robot/speed") == Some(NTValueType::Double(2.5))
+        });
+        assert_eq!(client.topics("/robot/"), ["/robot/speed"]);
+    }
+
+    #[test]
+    fn values_for_unannounced_topics_are_ignored() {
+        let (mut server, client) = connect();
+        client.get("/robot/speed");
+        server.expect_text("subscribe");
+        server.send_values(3, 1, &[1.0.into()]);
+        server.announce(4, "/robot/speed", "double");
+        server.send_values(4, 1, &[2.0.into()]);
+        wait_for("value", || client.get("/robot/speed").is_some());
+        assert_eq!(client.get("/robot/speed"), Some(NTValueType::Double(2.0)));
+    }
+
+    #[test]
+    fn publishing_announces_the_topic_first() {
+        let (mut server, mut client) = connect();
+        client.publish("/dash/count", NTValueType::Integer(4));
+        let params = server.expect_text("publish");
+        assert_eq!(params["name"], "/dash/count");
+        assert_eq!(params["type"], "int");
+        let pubuid = params["pubuid"].as_i64().unwrap();
+        let message = server.expect_binary(pubuid);
+        assert_eq!(message[2].as_u64(), Some(2));
+        assert_eq!(message[3].as_i64(), Some(4));
+        // Reads back without a round trip.
+        assert_eq!(client.get("/dash/count"), Some(NTValueType::Integer(4)));
+
+        // Same topic, other type: has to be published again.
+        client.publish("/dash/count", NTValueType::Double(4.5));
+        assert_eq!(server.expect_text("unpublish")["pubuid"], pubuid);
+        let params = server.expect_text("publish");
+        assert_eq!(params["type"], "double");
+        let message = server.expect_binary(params["pubuid"].as_i64().unwrap());
+        assert_eq!(message[3].as_f64(), Some(4.5));
+    }
+
+    #[test]
+    fn listeners_get_every_update_in_order() {
+        let (mut server, mut client) = connect();
+        client.listen("/robot/counter");
+        let params = server.expect_text("subscribe");
+        assert_eq!(params["options"]["all"], true);
+
+        server.announce(1, "/robot/counter", "int");
+        let values: Vec<rmpv::Value> = (0..=5).map(rmpv::Value::from).collect();
+        server.send_values(1, 2, &values);
+        let mut seen = Vec::new();
+        wait_for("all updates", || {
+            seen.extend(client.read_updates().into_iter().map(|u| u.value));
+            seen.len() >= values.len()
+        });
+        let expected: Vec<NTValueType> = (0..=5).map(NTValueType::Integer).collect();
+        assert_eq!(seen, expected);
+
+        client.unlisten("/robot/counter");
+        assert_eq!(
+            server.expect_text("unsubscribe")["subuid"],
+            params["subuid"]
+        );
+    }
+
+    #[test]
+    fn listening_starts_with_the_current_value() {
+        let (mut server, mut client) = connect();
+        client.subscribe("/robot/");
+        server.expect_text("subscribe");
+        server.announce(1, "/robot/mode", "string");
+        server.send_values(1, 4, &["auto".into()]);
+        wait_for("value", || client.get("/robot/mode").is_some());
+
+        client.listen_prefix("/robot/");
+        let subuid = server.expect_text("subscribe")["subuid"].clone();
+        let updates = client.read_updates();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].path, "/robot/mode");
+        assert_eq!(updates[0].value, NTValueType::String(String::from("auto")));
+
+        client.stop_prefix("/robot/");
+        assert_eq!(server.expect_text("unsubscribe")["subuid"], subuid);
+        server.send_values(1, 4, &["teleop".into()]);
+        wait_for("value", || {
+            client.get("/robot/mode") == Some(NTValueType::String(String::from("teleop")))
+        });
+        assert!(client.read_updates().is_empty());
+
+        // Overlapping listeners: one copy of the current value, one of each update.
+        client.listen_prefix("/robot/");
+        client.listen("/robot/mode");
+        client.listen_prefix("/");
+        assert_eq!(client.read_updates().len(), 1);
+        server.send_values(1, 4, &["disabled".into()]);
+        let mut updates = Vec::new();
+        wait_for("update", || {
+            updates.extend(client.read_updates());
+            !updates.is_empty()
+        });
+        thread::sleep(Duration::from_millis(50));
+        updates.extend(client.read_updates());
+        assert_eq!(updates.len(), 1);
+    }
+
+    #[test]
+    fn clock_follows_the_server() {
+        let (mut server, client) = connect();
+        let ping = server.expect_binary(TIME_SYNC_ID);
+        let server_us: i64 = 1_000_000_000_000;
+        let mut data = Vec::new();
+        let pong = rmpv::Value::Array(vec![
+            TIME_SYNC_ID.into(),
+            server_us.into(),
+            2.into(),
+            ping[3].clone(),
+        ]);
+        rmpv::encode::write_value(&mut data, &pong).unwrap();
+        server.socket().send(Message::binary(data)).unwrap();
+
+        wait_for("time sync", || client.now_us() >= server_us);
+        // Shouldn't be off by more than the round trip.
+        assert!(client.now_us() - server_us < TIMEOUT.as_micros() as i64);
+    }
+
+    #[test]
+    fn reconnects_and_subscribes_again() {
+        let (mut server, mut client) = connect();
+        client.subscribe("/SmartDashboard/");
+        client.publish("/dash/selected", NTValueType::String(String::from("Left")));
+        server.expect_text("subscribe");
+        server.announce(1, "/SmartDashboard/x", "double");
+        wait_for("topic", || !client.topics("/").is_empty());
+
+        // Robot reboots.
+        server.socket = None;
+        wait_for("client to notice", || !client.is_connected());
+        assert!(client.topics("/").is_empty());
+
+        server.accept();
+        let params = server.expect_text("subscribe");
+        assert_eq!(params["topics"], json!(["/SmartDashboard/"]));
+        assert_eq!(params["options"]["prefix"], true);
+        let params = server.expect_text("publish");
+        assert_eq!(params["name"], "/dash/selected");
+        let message = server.expect_binary(params["pubuid"].as_i64().unwrap());
+        assert_eq!(message[3].as_str(), Some("Left"));
+        wait_for("client to reconnect", || client.is_connected());
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use ntcore_sys::{
+    NT_AddPolledListener, NT_AddPolledListenerMultiple, NT_CreateListenerPoller,
+    NT_DisposeEventArray, NT_EventFlags_NT_EVENT_IMMEDIATE, NT_EventFlags_NT_EVENT_VALUE_ALL,
+    NT_GetTopicName, NT_Inst, NT_Listener, NT_ListenerPoller, NT_ReadListenerQueue,
+    NT_RemoveListener, NT_Topic, WPI_FreeString,
+};
+
+use crate::nt_util::{NTValueType, get_entry_handle, to_wpi_string, value_from_nt};
+
+const VALUE_EVENTS: u64 = NT_EventFlags_NT_EVENT_VALUE_ALL as u64;
+
+/// One value change, with ntcore's timestamp for it.
+#[derive(Debug)]
+pub struct ValueUpdate {
+    pub path: String,
+    pub value: NTValueType,
+    /// ntcore local time (same clock as NT_Now), microseconds.
+    pub timestamp_us: i64,
+}
+
+/// Every value change on the topics we listen to, read once per frame.
+/// Unlike polling with NT_Get*, this doesn't miss updates that happen between frames.
+pub struct NtEventStream {
+    inst: NT_Inst,
+    poller: NT_ListenerPoller,
+    listeners: HashMap<NT_Listener, Listening>,
+    /// Names of topics seen through prefix listeners.
+    topic_names: HashMap<NT_Topic, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Listening {
+    Topic(String),
+    Prefix(String),
+}
+
+impl NtEventStream {
+    pub fn new(inst: NT_Inst) -> Self {
+        Self {
+            inst,
+            poller: unsafe { NT_CreateListenerPoller(inst) },
+            listeners: HashMap::new(),
+            topic_names: HashMap::new(),
+        }
+    }
+
+    fn find(&self, listening: &Listening) -> Option<NT_Listener> {
+        self.listeners
+            .iter()
+            .find(|(_, l)| *l == listening)
+            .map(|(handle, _)| *handle)
+    }
+
+    pub fn is_listening(&self, path: &str) -> bool {
+        self.find(&Listening::Topic(path.to_string())).is_some()
+    }
+
+    /// Updates for every topic starting with `prefix` ("/" for everything).
+    pub fn listen_prefix(&mut self, prefix: &str) {
+        let listening = Listening::Prefix(prefix.to_string());
+        if self.find(&listening).is_some() {
+            return;
+        }
+        let prefixes = [to_wpi_string(prefix)];
+        let listener = unsafe {
+            NT_AddPolledListenerMultiple(
+                self.poller,
+                prefixes.as_ptr(),
+                prefixes.len(),
+                (VALUE_EVENTS | NT_EventFlags_NT_EVENT_IMMEDIATE as u64) as _,
+            )
+        };
+        self.listeners.insert(listener, listening);
+    }
+
+    pub fn stop_prefix(&mut self, prefix: &str) {
+        if let Some(listener) = self.find(&Listening::Prefix(prefix.to_string())) {
+            unsafe { NT_RemoveListener(listener) };
+            self.listeners.remove(&listener);
+        }
+    }
+
+    fn topic_name(&mut self, topic: NT_Topic) -> String {
+        self.topic_names
+            .entry(topic)
+            .or_insert_with(|| {
+                let mut name = to_wpi_string("");
+                unsafe { NT_GetTopicName(topic, &mut name) };
+                if name.str_.is_null() {
+                    return String::new();
+                }
+                let bytes = unsafe { std::slice::from_raw_parts(name.str_.cast::<u8>(), name.len) };
+                let s = String::from_utf8_lossy(bytes).into_owned();
+                unsafe { WPI_FreeString(&name) };
+                s
+            })
+            .clone()
+    }
+
+    /// Start getting updates for `path`, including its current value. Does nothing if we already are.
+    pub fn listen(&mut self, path: &str) {
+        if self.is_listening(path) {
+            return;
+        }
+        let listener = unsafe {
+            NT_AddPolledListener(
+                self.poller,
+                get_entry_handle(path, self.inst),
+                // `as _` since bindgen doesn't always pick the same int type for these.
+                (VALUE_EVENTS | NT_EventFlags_NT_EVENT_IMMEDIATE as u64) as _,
+            )
+        };
+        self.listeners
+            .insert(listener, Listening::Topic(path.to_string()));
+    }
+
+    pub fn unlisten(&mut self, path: &str) {
+        if let Some(listener) = self.find(&Listening::Topic(path.to_string())) {
+            unsafe { NT_RemoveListener(listener) };
+            self.listeners.remove(&listener);
+        }
+    }
+
+    /// A topic under two listeners (a graph and the logger's "/", or overlapping prefixes) gets an
+    /// event from each. Only the widest listener covering `path` hands them out.
+    fn covered_by_another(&self, listening: &Listening, path: &str) -> bool {
+        self.listeners.values().any(|other| match (listening, other) {
+            (Listening::Topic(_), Listening::Prefix(prefix)) => path.starts_with(prefix.as_str()),
+            (Listening::Prefix(mine), Listening::Prefix(prefix)) => {
+                prefix.len() < mine.len() && path.starts_with(prefix.as_str())
+            }
+            _ => false,
+        })
+    }
+
+    /// Everything that changed since the last call, oldest first.
+    pub fn read(&mut self) -> Vec<ValueUpdate> {
+        let mut len = 0usize;
+        let events = unsafe { NT_ReadListenerQueue(self.poller, &mut len) };
+        if events.is_null() {
+            return Vec::new();
+        }
+        let mut updates = Vec::new();
+        for event in unsafe { std::slice::from_raw_parts(events, len) } {
+            if event.flags as u64 & VALUE_EVENTS == 0 {
+                continue;
+            }
+            let data = unsafe { &event.data.valueData };
+            let Some(listening) = self.listeners.get(&event.listener).cloned() else {
+                continue;
+            };
+            let path = match &listening {
+                Listening::Topic(path) => path.clone(),
+                Listening::Prefix(_) => self.topic_name(data.topic),
+            };
+            if self.covered_by_another(&listening, &path) {
+                continue;
+            }
+            updates.push(ValueUpdate {
+                path,
+                value: value_from_nt(&data.value),
+                timestamp_us: data.value.last_change,
+            });
+        }
+        unsafe { NT_DisposeEventArray(events, len) };
+        updates
+    }
+}
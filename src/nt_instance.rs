@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use ntcore_sys::{
+    NT_CreateInstance, NT_DestroyInstance, NT_Inst, NT_IsConnected, NT_MultiSubscriber, NT_Now,
+    NT_SetBoolean, NT_SetBooleanArray, NT_SetDouble, NT_SetDoubleArray, NT_SetFloat,
+    NT_SetFloatArray, NT_SetInteger, NT_SetIntegerArray, NT_SetServer, NT_SetServerTeam,
+    NT_SetString, NT_SetStringArray, NT_StartClient4, NT_StartServer, NT_SubscribeMultiple,
+};
+
+use crate::{
+    data_source::DataSource,
+    nt_events::{NtEventStream, ValueUpdate},
+    nt_util::{NTValueType, get_entry_handle, get_value, to_wpi_string, topic_names},
+};
+
+/// A live ntcoreffi instance. The dashboard runs it as a client connected to the robot,
+/// tests and the simulator run one as a server.
+pub struct NtInstance {
+    inst: NT_Inst,
+    events: NtEventStream,
+    subscribers: HashMap<String, NT_MultiSubscriber>,
+}
+
+impl NtInstance {
+    fn new() -> Self {
+        let inst = unsafe { NT_CreateInstance() };
+        Self {
+            inst,
+            events: NtEventStream::new(inst),
+            subscribers: HashMap::new(),
+        }
+    }
+
+    /// Starts an NT4 client called `name`. Call `connect` or `connect_to` to point it somewhere.
+    pub fn start_client(name: &str) -> Self {
+        let s = Self::new();
+        unsafe { NT_StartClient4(s.inst, &to_wpi_string(name)) };
+        s
+    }
+
+    /// Starts an NT4 server on `listen_address` ("" for all interfaces), like the robot runs.
+    /// Persistent topics get saved to `persist_file`.
+    pub fn start_server(persist_file: &str, listen_address: &str, port: u32) -> Self {
+        let s = Self::new();
+        unsafe {
+            NT_StartServer(
+                s.inst,
+                &to_wpi_string(persist_file),
+                &to_wpi_string(listen_address),
+                // NT3 port, which we don't use.
+                0,
+                port,
+            )
+        };
+        s
+    }
+
+    /// Connect a client to a server by address instead of team number.
+    pub fn connect_to(&mut self, address: &str, port: u32) {
+        unsafe { NT_SetServer(self.inst, &to_wpi_string(address), port) };
+    }
+}
+
+impl Drop for NtInstance {
+    fn drop(&mut self) {
+        // Stops the client/server and frees everything made on the instance.
+        unsafe { NT_DestroyInstance(self.inst) };
+    }
+}
+
+impl DataSource for NtInstance {
+    fn get(&self, path: &str) -> Option<NTValueType> {
+        match get_value(path, self.inst) {
+            NTValueType::Unknown => None,
+            value => Some(value),
+        }
+    }
+
+    fn topics(&self, prefix: &str) -> Vec<String> {
+        topic_names(prefix, self.inst)
+    }
+
+    fn subscribe(&mut self, prefix: &str) {
+        if self.subscribers.contains_key(prefix) {
+            return;
+        }
+        let prefixes = [to_wpi_string(prefix)];
+        let subscriber = unsafe {
+            NT_SubscribeMultiple(
+                self.inst,
+                prefixes.as_ptr(),
+                prefixes.len(),
+                std::ptr::null(),
+            )
+        };
+        self.subscribers.insert(prefix.to_string(), subscriber);
+    }
+
+    fn listen(&mut self, path: &str) {
+        self.events.listen(path);
+    }
+
+    fn unlisten(&mut self, path: &str) {
+        self.events.unlisten(path);
+    }
+
+    fn listen_prefix(&mut self, prefix: &str) {
+        self.events.listen_prefix(prefix);
+    }
+
+    fn stop_prefix(&mut self, prefix: &str) {
+        self.events.stop_prefix(prefix);
+    }
+
+    fn read_updates(&mut self) -> Vec<ValueUpdate> {
+        self.events.read()
+    }
+
+    fn publish(&mut self, path: &str, value: NTValueType) {
+        let entry = get_entry_handle(path, self.inst);
+        unsafe {
+            match value {
+                NTValueType::Unknown => 0,
+                NTValueType::Boolean(b) => NT_SetBoolean(entry, 0, b as i32),
+                NTValueType::Double(d) => NT_SetDouble(entry, 0, d),
+                NTValueType::Float(f) => NT_SetFloat(entry, 0, f),
+                NTValueType::Integer(i) => NT_SetInteger(entry, 0, i as i64),
+                NTValueType::String(s) => NT_SetString(entry, 0, &to_wpi_string(&s)),
+                NTValueType::BooleanArray(arr) => {
+                    let arr: Vec<i32> = arr.iter().map(|b| *b as i32).collect();
+                    NT_SetBooleanArray(entry, 0, arr.as_ptr(), arr.len())
+                }
+                NTValueType::DoubleArray(arr) => {
+                    NT_SetDoubleArray(entry, 0, arr.as_ptr(), arr.len())
+                }
+                NTValueType::FloatArray(arr) => NT_SetFloatArray(entry, 0, arr.as_ptr(), arr.len()),
+                NTValueType::IntegerArray(arr) => {
+                    let arr: Vec<i64> = arr.iter().map(|i| *i as i64).collect();
+                    NT_SetIntegerArray(entry, 0, arr.as_ptr(), arr.len())
+                }
+                NTValueType::StringArray(arr) => {
+                    // These point into `arr`, which outlives the call.
+                    let strings: Vec<_> = arr.iter().map(|s| to_wpi_string(s)).collect();
+                    NT_SetStringArray(entry, 0, strings.as_ptr(), strings.len())
+                }
+            }
+        };
+    }
+
+    fn now_us(&self) -> i64 {
+        unsafe { NT_Now() }
+    }
+
+    fn is_connected(&self) -> bool {
+        unsafe { NT_IsConnected(self.inst) != 0 }
+    }
+
+    // connects to rio, or to the simulator on this computer for team 0
+    fn connect(&mut self, team: u32, port: u32) {
+        if team == 0 {
+            self.connect_to("localhost", port);
+            return;
+        }
+        unsafe { NT_SetServerTeam(self.inst, team, port) };
+    }
+}
@@ -3,6 +3,47 @@ pub const LUNITE_COUNT: &str = "/SmartDashboard/luniteCount";
 pub const CURRENT_STATE: &str = "/SmartDashboard/currentState";
 pub const ROBOT_2D_POSITION: &str = "/SmartDashboard/robot2DPosition";
 pub const KNOWN_LUNITE_POSITIONS: &str = "/SmartDashboard/knownLunitePositions";
-pub const AUTO_CHOOSER_OPTIONS: &str = "/SmartDashboard/autoChooser/options";
-pub const AUTO_CHOOSER_ACTIVE: &str = "/SmartDashboard/autoChooser/active";
+// Optional, [confidence, age, id] for each lunite in KNOWN_LUNITE_POSITIONS.
+pub const KNOWN_LUNITE_METADATA: &str = "/SmartDashboard/knownLuniteMetadata";
+pub const TARGET_LUNITE_ID: &str = "/SmartDashboard/targetLuniteId";
+// Optional, same JSON as the bindings file. Overrides it when the robot publishes it.
+pub const INPUT_BINDINGS: &str = "/SmartDashboard/inputBindings";
+// SendableChooser table, see sendable_key for the topics under it.
+pub const AUTO_CHOOSER: &str = "/SmartDashboard/autoChooser";
 pub const FMS_IS_RED_ALLIANCE: &str = "/FMSInfo/IsRedAlliance";
+pub const FMS_CONTROL_DATA: &str = "/FMSInfo/FMSControlData";
+pub const FMS_EVENT_NAME: &str = "/FMSInfo/EventName";
+pub const FMS_GAME_SPECIFIC_MESSAGE: &str = "/FMSInfo/GameSpecificMessage";
+pub const FMS_MATCH_NUMBER: &str = "/FMSInfo/MatchNumber";
+pub const FMS_MATCH_TYPE: &str = "/FMSInfo/MatchType";
+pub const FMS_REPLAY_NUMBER: &str = "/FMSInfo/ReplayNumber";
+pub const FMS_STATION_NUMBER: &str = "/FMSInfo/StationNumber";
+
+// Limelight MegaTag pose estimates, published under the limelight's hostname.
+pub fn limelight_botpose_blue(limelight: &str) -> String {
+    format!("/{}/botpose_wpiblue", limelight)
+}
+pub fn limelight_botpose_red(limelight: &str) -> String {
+    format!("/{}/botpose_wpired", limelight)
+}
+
+// Published by us: whether the robot is inside a field model zone.
+pub fn zone_state(zone: &str) -> String {
+    format!("/FrcUI/zones/{}", zone)
+}
+
+// Driver Station joystick data, republished by the robot code since WPILib doesn't put it on NT.
+pub fn joystick_axes(port: usize) -> String {
+    format!("/FrcUI/joysticks/{}/axes", port)
+}
+pub fn joystick_buttons(port: usize) -> String {
+    format!("/FrcUI/joysticks/{}/buttons", port)
+}
+pub fn joystick_povs(port: usize) -> String {
+    format!("/FrcUI/joysticks/{}/povs", port)
+}
+
+// Topics under a Sendable's table, like "options" or ".type".
+pub fn sendable_key(table: &str, key: &str) -> String {
+    format!("{}/{}", table, key)
+}
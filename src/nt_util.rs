@@ -1,8 +1,11 @@
 use std::{collections::HashMap, ffi::c_void};
 
 use ntcore_sys::{
-    NT_AddListener, NT_Event, NT_EventFlags_NT_EVENT_VALUE_ALL, NT_GetEntry, NT_Handle, NT_Inst,
-    NT_Type_NT_BOOLEAN, NT_Type_NT_DOUBLE, NT_Type_NT_DOUBLE_ARRAY, NT_Type_NT_STRING, WPI_String,
+    NT_AddListener, NT_DisposeTopicInfoArray, NT_DisposeValue, NT_Event,
+    NT_EventFlags_NT_EVENT_VALUE_ALL, NT_GetEntry, NT_GetEntryValue, NT_GetTopicInfos, NT_Handle,
+    NT_Inst, NT_Type_NT_BOOLEAN, NT_Type_NT_BOOLEAN_ARRAY, NT_Type_NT_DOUBLE,
+    NT_Type_NT_DOUBLE_ARRAY, NT_Type_NT_FLOAT, NT_Type_NT_FLOAT_ARRAY, NT_Type_NT_INTEGER,
+    NT_Type_NT_INTEGER_ARRAY, NT_Type_NT_STRING, NT_Type_NT_STRING_ARRAY, NT_Value, WPI_String,
 };
 
 pub type ListenedValues = HashMap<String, NTValueType>;
@@ -21,7 +24,7 @@ pub fn from_wpi_string(s: WPI_String) -> String {
     return original_string.clone();
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum NTValueType {
     Unknown,
     Boolean(bool),
@@ -94,7 +97,7 @@ pub extern "C" fn nt_update(data: *mut c_void, event: *const NT_Event) {
 pub fn format_game_time(time: Option<f64>) -> String {
     if let Some(f) = time {
         let time_s = f.ceil() as i32;
-        format!("{}:{}", time_s / 60, time_s % 60)
+        format!("{}:{:02}", time_s / 60, time_s % 60)
     } else {
         String::from("WAITING FOR MATCH START")
     }
@@ -103,3 +106,85 @@ pub fn format_game_time(time: Option<f64>) -> String {
 pub fn get_entry_handle(path: &str, inst: u32) -> NT_Handle {
     unsafe { NT_GetEntry(inst, &to_wpi_string(path)) }
 }
+
+fn copy_wpi_string(s: &WPI_String) -> String {
+    if s.str_.is_null() {
+        return String::new();
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(s.str_.cast::<u8>(), s.len) };
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Copies any NT value out into our own type. Doesn't take ownership of `value`.
+#[allow(non_upper_case_globals)]
+pub fn value_from_nt(value: &NT_Value) -> NTValueType {
+    // Slices from ntcore arrays, which can be null when empty.
+    fn slice<'a, T>(arr: *const T, len: usize) -> &'a [T] {
+        if arr.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(arr, len) }
+        }
+    }
+    let data = &value.data;
+    unsafe {
+        match value.type_ {
+            NT_Type_NT_BOOLEAN => NTValueType::Boolean(data.v_boolean != 0),
+            NT_Type_NT_DOUBLE => NTValueType::Double(data.v_double),
+            NT_Type_NT_FLOAT => NTValueType::Float(data.v_float),
+            NT_Type_NT_INTEGER => NTValueType::Integer(data.v_int as i32),
+            NT_Type_NT_STRING => NTValueType::String(copy_wpi_string(&data.v_string)),
+            NT_Type_NT_BOOLEAN_ARRAY => NTValueType::BooleanArray(
+                slice(data.arr_boolean.arr, data.arr_boolean.size)
+                    .iter()
+                    .map(|b| *b != 0)
+                    .collect(),
+            ),
+            NT_Type_NT_DOUBLE_ARRAY => {
+                NTValueType::DoubleArray(slice(data.arr_double.arr, data.arr_double.size).to_vec())
+            }
+            NT_Type_NT_FLOAT_ARRAY => {
+                NTValueType::FloatArray(slice(data.arr_float.arr, data.arr_float.size).to_vec())
+            }
+            NT_Type_NT_INTEGER_ARRAY => NTValueType::IntegerArray(
+                slice(data.arr_int.arr, data.arr_int.size)
+                    .iter()
+                    .map(|i| *i as i32)
+                    .collect(),
+            ),
+            NT_Type_NT_STRING_ARRAY => NTValueType::StringArray(
+                slice(data.arr_string.arr, data.arr_string.size)
+                    .iter()
+                    .map(copy_wpi_string)
+                    .collect(),
+            ),
+            _ => NTValueType::Unknown,
+        }
+    }
+}
+
+/// Reads whatever value is at `path`, Unknown if there's nothing there.
+pub fn get_value(path: &str, inst: NT_Inst) -> NTValueType {
+    let mut value = std::mem::MaybeUninit::<NT_Value>::zeroed();
+    unsafe { NT_GetEntryValue(get_entry_handle(path, inst), value.as_mut_ptr()) };
+    let mut value = unsafe { value.assume_init() };
+    let converted = value_from_nt(&value);
+    unsafe { NT_DisposeValue(&mut value) };
+    converted
+}
+
+/// Names of all topics we know about under `prefix`.
+/// Only includes topics something is subscribed to, so subscribe to the prefix first.
+pub fn topic_names(prefix: &str, inst: NT_Inst) -> Vec<String> {
+    let mut count = 0usize;
+    let infos = unsafe { NT_GetTopicInfos(inst, &to_wpi_string(prefix), 0, &mut count) };
+    if infos.is_null() {
+        return Vec::new();
+    }
+    let names = unsafe { std::slice::from_raw_parts(infos, count) }
+        .iter()
+        .map(|info| copy_wpi_string(&info.name))
+        .collect();
+    unsafe { NT_DisposeTopicInfoArray(infos, count) };
+    names
+}
@@ -0,0 +1,229 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use opencv::videoio::{
+    CAP_ANY, CAP_PROP_POS_MSEC, VideoCapture, VideoCaptureTrait, VideoCaptureTraitConst,
+};
+
+use crate::{
+    data_source::DataSource,
+    nt_events::ValueUpdate,
+    nt_util::{ListenedValues, NTValueType},
+    wpilog::read_wpilog,
+};
+
+pub const SPEEDS: [f64; 6] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+const VIDEO_EXTENSIONS: [&str; 3] = ["mp4", "avi", "mkv"];
+/// How far a video can drift from the log before we seek it.
+const VIDEO_MAX_DRIFT_MS: f64 = 250.0;
+
+/// Plays back a .wpilog as a data source, in place of the live NT client.
+pub struct Replay {
+    pub path: PathBuf,
+    /// Sorted by timestamp.
+    records: Vec<ValueUpdate>,
+    pub start_us: i64,
+    pub end_us: i64,
+    /// Current spot on the timeline, log time in microseconds.
+    pub position_us: f64,
+    pub playing: bool,
+    pub speed: f64,
+    /// Index of the next record to apply.
+    cursor: usize,
+    /// Values at the current position.
+    values: ListenedValues,
+    /// Records applied since the last `read_updates`.
+    pending: Vec<ValueUpdate>,
+    /// Set for a frame after jumping backwards, so graphs can throw out their samples.
+    pub rewound: bool,
+    /// Camera recordings found next to the log, by camera name.
+    pub cameras: HashMap<String, VideoCapture>,
+    last_time: Option<f64>,
+}
+
+/// Looks for recordings named like "<log name>_<camera name>.mp4" next to the log.
+/// They're assumed to start when the log does.
+fn find_videos(log: &Path, camera_names: &[String]) -> HashMap<String, VideoCapture> {
+    let mut cameras = HashMap::new();
+    let stem = log.file_stem().unwrap_or_default().to_string_lossy();
+    for name in camera_names {
+        for ext in VIDEO_EXTENSIONS {
+            let video = log.with_file_name(format!("{}_{}.{}", stem, name, ext));
+            if !video.exists() {
+                continue;
+            }
+            match VideoCapture::from_file(&video.to_string_lossy(), CAP_ANY) {
+                Ok(cap) => {
+                    cameras.insert(name.clone(), cap);
+                }
+                Err(e) => println!("Failed to open recording {}: {}", video.display(), e),
+            }
+            break;
+        }
+    }
+    cameras
+}
+
+impl Replay {
+    pub fn load(path: &Path, camera_names: &[String]) -> Result<Self, String> {
+        let data =
+            fs::read(path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        let mut records =
+            read_wpilog(&data).map_err(|e| format!("bad log {}: {}", path.display(), e))?;
+        if records.is_empty() {
+            return Err(format!("{} has no values we can show", path.display()));
+        }
+        // Stable, so values with the same timestamp stay in file order.
+        records.sort_by_key(|r| r.timestamp_us);
+        let start_us = records[0].timestamp_us;
+        let end_us = records[records.len() - 1].timestamp_us;
+        Ok(Self {
+            path: path.to_path_buf(),
+            records,
+            start_us,
+            end_us,
+            position_us: start_us as f64,
+            playing: true,
+            speed: 1.0,
+            cursor: 0,
+            values: ListenedValues::new(),
+            pending: Vec::new(),
+            rewound: false,
+            cameras: find_videos(path, camera_names),
+            last_time: None,
+        })
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Seconds into the log.
+    pub fn elapsed_s(&self) -> f64 {
+        (self.position_us - self.start_us as f64) / 1e6
+    }
+
+    pub fn duration_s(&self) -> f64 {
+        (self.end_us - self.start_us) as f64 / 1e6
+    }
+
+    pub fn seek_s(&mut self, elapsed_s: f64) {
+        self.position_us = (self.start_us as f64 + elapsed_s * 1e6)
+            .clamp(self.start_us as f64, self.end_us as f64);
+    }
+
+    /// Call every frame, before reading anything. Moves the timeline along.
+    pub fn advance(&mut self, now: f64) {
+        let dt = self.last_time.map_or(0.0, |t| now - t);
+        self.last_time = Some(now);
+        if self.playing {
+            self.position_us += dt * 1e6 * self.speed;
+            if self.position_us >= self.end_us as f64 {
+                self.position_us = self.end_us as f64;
+                self.playing = false;
+            }
+        }
+
+        // Going backwards means replaying from the start, since values only store the latest.
+        self.rewound =
+            self.cursor > 0 && self.records[self.cursor - 1].timestamp_us as f64 > self.position_us;
+        if self.rewound {
+            self.values.clear();
+            self.pending.clear();
+            self.cursor = 0;
+        }
+        while let Some(record) = self.records.get(self.cursor)
+            && record.timestamp_us as f64 <= self.position_us
+        {
+            self.values
+                .insert(record.path.clone(), record.value.clone());
+            self.pending.push(ValueUpdate {
+                path: record.path.clone(),
+                value: record.value.clone(),
+                timestamp_us: record.timestamp_us,
+            });
+            self.cursor += 1;
+        }
+
+        self.sync_cameras();
+    }
+
+    /// Keeps recordings lined up with the timeline. The camera panel reads a frame per repaint,
+    /// so they drift while playing and need holding in place while paused.
+    fn sync_cameras(&mut self) {
+        let want_ms = self.elapsed_s() * 1000.0;
+        for cap in self.cameras.values_mut() {
+            let at_ms = cap.get(CAP_PROP_POS_MSEC).unwrap_or(0.0);
+            if !self.playing || (at_ms - want_ms).abs() > VIDEO_MAX_DRIFT_MS {
+                let _ = cap.set(CAP_PROP_POS_MSEC, want_ms);
+            }
+        }
+    }
+}
+
+/// Everything in the log comes through `read_updates` whether it's listened to or not,
+/// and publishing does nothing since you can't change a recording.
+impl DataSource for Replay {
+    fn get(&self, path: &str) -> Option<NTValueType> {
+        self.values.get(path).cloned()
+    }
+
+    fn topics(&self, prefix: &str) -> Vec<String> {
+        self.values
+            .keys()
+            .filter(|k| k.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+
+    fn subscribe(&mut self, _prefix: &str) {}
+
+    fn listen(&mut self, _path: &str) {}
+
+    fn unlisten(&mut self, _path: &str) {}
+
+    fn listen_prefix(&mut self, _prefix: &str) {}
+
+    fn stop_prefix(&mut self, _prefix: &str) {}
+
+    fn read_updates(&mut self) -> Vec<ValueUpdate> {
+        std::mem::take(&mut self.pending)
+    }
+
+    fn publish(&mut self, _path: &str, _value: NTValueType) {}
+
+    fn now_us(&self) -> i64 {
+        self.position_us as i64
+    }
+
+    fn is_connected(&self) -> bool {
+        false
+    }
+}
+
+/// .wpilog files in `dir`, newest first.
+pub fn list_logs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut logs: Vec<(PathBuf, std::time::SystemTime)> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "wpilog"))
+        .map(|p| {
+            let modified = fs::metadata(&p)
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::UNIX_EPOCH);
+            (p, modified)
+        })
+        .collect();
+    logs.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    logs.into_iter().map(|(p, _)| p).collect()
+}
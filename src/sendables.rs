@@ -0,0 +1,95 @@
+use crate::{
+    data_source::DataSource,
+    nt_paths,
+    nt_util::{ListenedValues, NTValueType},
+};
+
+pub const SMARTDASHBOARD_PREFIX: &str = "/SmartDashboard/";
+/// Rescanning topics is slower than reading values, so don't do it every frame.
+const SCAN_INTERVAL_S: f64 = 1.0;
+
+/// Something the robot put on SmartDashboard with `SmartDashboard.putData`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sendable {
+    pub table: String,
+    /// From the `.type` topic, like "Command" or "PIDController".
+    pub type_name: String,
+    /// Topics under the table, relative to it. Includes nested ones like "root/arm/angle".
+    pub keys: Vec<String>,
+}
+
+impl Sendable {
+    /// Table without the SmartDashboard prefix, which is what the robot code called it.
+    pub fn name(&self) -> &str {
+        self.table
+            .strip_prefix(SMARTDASHBOARD_PREFIX)
+            .unwrap_or(&self.table)
+    }
+}
+
+/// Groups topic names into Sendables by their `.type` topics.
+/// Tables nested in another Sendable (like Mechanism2d ligaments) belong to the outer one.
+pub fn find_sendables(topics: &[String]) -> Vec<Sendable> {
+    let mut tables: Vec<&str> = topics
+        .iter()
+        .filter_map(|t| t.strip_suffix("/.type"))
+        .collect();
+    tables.sort();
+    let mut outer: Vec<&str> = Vec::new();
+    for table in tables {
+        if !outer
+            .iter()
+            .any(|o| table.starts_with(o) && table[o.len()..].starts_with('/'))
+        {
+            outer.push(table);
+        }
+    }
+
+    outer
+        .into_iter()
+        .map(|table| {
+            let prefix = format!("{}/", table);
+            let mut keys: Vec<String> = topics
+                .iter()
+                .filter_map(|t| t.strip_prefix(&prefix))
+                .map(str::to_string)
+                .collect();
+            keys.sort();
+            Sendable {
+                table: table.to_string(),
+                type_name: String::new(),
+                keys,
+            }
+        })
+        .collect()
+}
+
+#[derive(Default)]
+pub struct Sendables {
+    pub list: Vec<Sendable>,
+    last_scan: Option<f64>,
+}
+
+impl Sendables {
+    /// Call every frame. Finds new Sendables every so often and reads all their values.
+    /// The source needs to be subscribed to SMARTDASHBOARD_PREFIX for them to show up.
+    pub fn update(&mut self, values: &mut ListenedValues, source: &dyn DataSource, now: f64) {
+        if self.last_scan.is_none_or(|t| now - t > SCAN_INTERVAL_S) {
+            self.last_scan = Some(now);
+            self.list = find_sendables(&source.topics(SMARTDASHBOARD_PREFIX));
+        }
+
+        for sendable in &mut self.list {
+            for key in &sendable.keys {
+                let path = nt_paths::sendable_key(&sendable.table, key);
+                let value = source.get(&path).unwrap_or(NTValueType::Unknown);
+                values.insert(path, value);
+            }
+            if let Some(NTValueType::String(t)) =
+                values.get(&nt_paths::sendable_key(&sendable.table, ".type"))
+            {
+                sendable.type_name = t.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::{collections::VecDeque, fs, path::Path};
+
+use serde::Deserialize;
+
+const HISTORY_LEN: usize = 50;
+
+/// The robot's state machine as the dashboard should draw it.
+/// Comes from a JSON file since the states change with the robot code.
+#[derive(Debug, Default, Deserialize)]
+pub struct StateMachineConfig {
+    pub states: Vec<String>,
+    /// (from, to) pairs.
+    #[serde(default)]
+    pub transitions: Vec<(String, String)>,
+}
+
+impl StateMachineConfig {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text)
+            .map_err(|e| format!("bad state machine config {}: {}", path.display(), e))
+    }
+
+    pub fn index_of(&self, state: &str) -> Option<usize> {
+        self.states.iter().position(|s| s == state)
+    }
+
+    pub fn allows(&self, from: &str, to: &str) -> bool {
+        self.transitions.iter().any(|(f, t)| f == from && t == to)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateChange {
+    pub state: String,
+    /// egui time (seconds) we first saw this state.
+    pub entered_at: f64,
+    /// None while it's still the current state.
+    pub left_at: Option<f64>,
+}
+
+impl StateChange {
+    pub fn duration(&self, now: f64) -> f64 {
+        self.left_at.unwrap_or(now) - self.entered_at
+    }
+}
+
+/// Recent state changes, newest last.
+#[derive(Default)]
+pub struct StateHistory {
+    pub changes: VecDeque<StateChange>,
+}
+
+impl StateHistory {
+    /// Call every frame with the current state. Returns true if it changed.
+    pub fn update(&mut self, state: &str, now: f64) -> bool {
+        if self.current().is_some_and(|c| c.state == state) {
+            return false;
+        }
+        if let Some(last) = self.changes.back_mut() {
+            last.left_at = Some(now);
+        }
+        self.changes.push_back(StateChange {
+            state: state.to_string(),
+            entered_at: now,
+            left_at: None,
+        });
+        while self.changes.len() > HISTORY_LEN {
+            self.changes.pop_front();
+        }
+        true
+    }
+
+    pub fn current(&self) -> Option<&StateChange> {
+        self.changes.back()
+    }
+
+    pub fn previous(&self) -> Option<&StateChange> {
+        self.changes.iter().rev().nth(1)
+    }
+}
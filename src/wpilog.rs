@@ -0,0 +1,289 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::{nt_events::ValueUpdate, nt_util::NTValueType};
+
+// See https://github.com/wpilibsuite/allwpilib/blob/main/wpiutil/doc/datalog.adoc
+const MAGIC: &[u8] = b"WPILOG";
+const VERSION: u16 = 0x0100;
+const CONTROL_START: u8 = 0;
+const CONTROL_FINISH: u8 = 1;
+
+/// WPILOG type string for a value, None for ones we can't log.
+pub fn type_name(value: &NTValueType) -> Option<&'static str> {
+    Some(match value {
+        NTValueType::Boolean(_) => "boolean",
+        NTValueType::Double(_) => "double",
+        NTValueType::Float(_) => "float",
+        NTValueType::Integer(_) => "int64",
+        NTValueType::String(_) => "string",
+        NTValueType::BooleanArray(_) => "boolean[]",
+        NTValueType::DoubleArray(_) => "double[]",
+        NTValueType::FloatArray(_) => "float[]",
+        NTValueType::IntegerArray(_) => "int64[]",
+        NTValueType::StringArray(_) => "string[]",
+        NTValueType::Unknown => return None,
+    })
+}
+
+pub fn encode_value(value: &NTValueType) -> Vec<u8> {
+    let mut out = Vec::new();
+    match value {
+        NTValueType::Unknown => {}
+        NTValueType::Boolean(b) => out.push(*b as u8),
+        NTValueType::Double(d) => out.extend(d.to_le_bytes()),
+        NTValueType::Float(f) => out.extend(f.to_le_bytes()),
+        NTValueType::Integer(i) => out.extend((*i as i64).to_le_bytes()),
+        NTValueType::String(s) => out.extend(s.as_bytes()),
+        NTValueType::BooleanArray(arr) => out.extend(arr.iter().map(|b| *b as u8)),
+        NTValueType::DoubleArray(arr) => arr.iter().for_each(|d| out.extend(d.to_le_bytes())),
+        NTValueType::FloatArray(arr) => arr.iter().for_each(|f| out.extend(f.to_le_bytes())),
+        NTValueType::IntegerArray(arr) => arr
+            .iter()
+            .for_each(|i| out.extend((*i as i64).to_le_bytes())),
+        NTValueType::StringArray(arr) => {
+            out.extend((arr.len() as u32).to_le_bytes());
+            for s in arr {
+                out.extend((s.len() as u32).to_le_bytes());
+                out.extend(s.as_bytes());
+            }
+        }
+    }
+    out
+}
+
+/// Smallest number of bytes (at least 1) that holds `v`.
+fn byte_len(v: u64) -> usize {
+    (8 - v.leading_zeros() as usize / 8).max(1)
+}
+
+/// Writes a .wpilog file that AdvantageScope and the WPILib tools can open.
+pub struct WpilogWriter<W: Write> {
+    out: W,
+    /// Entry name -> (id, type)
+    entries: HashMap<String, (u32, &'static str)>,
+    next_id: u32,
+}
+
+impl WpilogWriter<BufWriter<File>> {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), "")
+    }
+}
+
+impl<W: Write> WpilogWriter<W> {
+    pub fn new(mut out: W, extra_header: &str) -> io::Result<Self> {
+        out.write_all(MAGIC)?;
+        out.write_all(&VERSION.to_le_bytes())?;
+        out.write_all(&(extra_header.len() as u32).to_le_bytes())?;
+        out.write_all(extra_header.as_bytes())?;
+        Ok(Self {
+            out,
+            entries: HashMap::new(),
+            next_id: 1,
+        })
+    }
+
+    fn write_record(&mut self, entry: u32, timestamp_us: i64, payload: &[u8]) -> io::Result<()> {
+        let timestamp = timestamp_us.max(0) as u64;
+        let id_len = byte_len(entry as u64);
+        let size_len = byte_len(payload.len() as u64);
+        let time_len = byte_len(timestamp);
+        let header = (id_len - 1) | ((size_len - 1) << 2) | ((time_len - 1) << 4);
+        self.out.write_all(&[header as u8])?;
+        self.out.write_all(&entry.to_le_bytes()[..id_len])?;
+        self.out
+            .write_all(&(payload.len() as u32).to_le_bytes()[..size_len])?;
+        self.out.write_all(&timestamp.to_le_bytes()[..time_len])?;
+        self.out.write_all(payload)
+    }
+
+    fn start_entry(
+        &mut self,
+        name: &str,
+        type_name: &'static str,
+        timestamp_us: i64,
+    ) -> io::Result<u32> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut payload = vec![CONTROL_START];
+        payload.extend(id.to_le_bytes());
+        for s in [name, type_name, ""] {
+            payload.extend((s.len() as u32).to_le_bytes());
+            payload.extend(s.as_bytes());
+        }
+        self.write_record(0, timestamp_us, &payload)?;
+        self.entries.insert(name.to_string(), (id, type_name));
+        Ok(id)
+    }
+
+    /// Logs a value, starting a new entry the first time we see `name` (or if its type changed).
+    pub fn append(&mut self, name: &str, value: &NTValueType, timestamp_us: i64) -> io::Result<()> {
+        let Some(type_name) = type_name(value) else {
+            return Ok(());
+        };
+        let id = match self.entries.get(name).copied() {
+            Some((id, t)) if t == type_name => id,
+            Some((old, _)) => {
+                let mut payload = vec![CONTROL_FINISH];
+                payload.extend(old.to_le_bytes());
+                self.write_record(0, timestamp_us, &payload)?;
+                self.start_entry(name, type_name, timestamp_us)?
+            }
+            None => self.start_entry(name, type_name, timestamp_us)?,
+        };
+        self.write_record(id, timestamp_us, &encode_value(value))
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Walks through a byte slice, for reading logs.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn done(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn take(&mut self, n: usize) -> io::Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + n)
+            .ok_or_else(|| invalid(format!("log cut off at byte {}", self.pos)))?;
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    /// Little endian unsigned int of `n` bytes.
+    fn uint(&mut self, n: usize) -> io::Result<u64> {
+        let mut buf = [0u8; 8];
+        buf[..n].copy_from_slice(self.take(n)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        let len = self.uint(4)? as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+
+    /// (entry id, timestamp in microseconds, payload)
+    fn record(&mut self) -> io::Result<(u32, i64, &'a [u8])> {
+        let header = self.take(1)?[0] as usize;
+        let id = self.uint((header & 0x3) + 1)? as u32;
+        let size = self.uint(((header >> 2) & 0x3) + 1)? as usize;
+        let timestamp = self.uint(((header >> 4) & 0x7) + 1)? as i64;
+        Ok((id, timestamp, self.take(size)?))
+    }
+}
+
+/// Turns a record payload back into a value, None for types we don't show (raw, structs...).
+pub fn decode_value(type_name: &str, payload: &[u8]) -> Option<NTValueType> {
+    let f64s = || {
+        payload
+            .chunks_exact(8)
+            .map(|c| f64::from_le_bytes(c.try_into().unwrap()))
+    };
+    let i64s = || {
+        payload
+            .chunks_exact(8)
+            .map(|c| i64::from_le_bytes(c.try_into().unwrap()))
+    };
+    let f32s = || {
+        payload
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+    };
+    Some(match type_name {
+        "boolean" => NTValueType::Boolean(*payload.first()? != 0),
+        "double" => NTValueType::Double(f64s().next()?),
+        "float" => NTValueType::Float(f32s().next()?),
+        "int64" => NTValueType::Integer(i64s().next()? as i32),
+        "string" | "json" => NTValueType::String(String::from_utf8_lossy(payload).into_owned()),
+        "boolean[]" => NTValueType::BooleanArray(payload.iter().map(|b| *b != 0).collect()),
+        "double[]" => NTValueType::DoubleArray(f64s().collect()),
+        "float[]" => NTValueType::FloatArray(f32s().collect()),
+        "int64[]" => NTValueType::IntegerArray(i64s().map(|i| i as i32).collect()),
+        "string[]" => {
+            let mut reader = Reader::new(payload);
+            let count = reader.uint(4).ok()?;
+            let strings: io::Result<Vec<String>> = (0..count).map(|_| reader.string()).collect();
+            NTValueType::StringArray(strings.ok()?)
+        }
+        _ => return None,
+    })
+}
+
+/// Every value in a .wpilog file, in the order they were written.
+pub fn read_wpilog(data: &[u8]) -> io::Result<Vec<ValueUpdate>> {
+    let mut reader = Reader::new(data);
+    if reader.take(MAGIC.len()).ok() != Some(MAGIC) {
+        return Err(invalid(String::from("not a .wpilog file")));
+    }
+    let version = reader.uint(2)? as u16;
+    if version >> 8 != VERSION >> 8 {
+        return Err(invalid(format!(
+            "unsupported .wpilog version {:#06x}",
+            version
+        )));
+    }
+    let extra_header_len = reader.uint(4)? as usize;
+    reader.take(extra_header_len)?;
+
+    // Entry id -> (NT path, type)
+    let mut entries: HashMap<u32, (String, String)> = HashMap::new();
+    let mut updates = Vec::new();
+    while !reader.done() {
+        // A log from a crash can end in the middle of a record, keep everything before it.
+        let Ok((id, timestamp_us, payload)) = reader.record() else {
+            break;
+        };
+        if id != 0 {
+            if let Some((name, type_name)) = entries.get(&id)
+                && let Some(value) = decode_value(type_name, payload)
+            {
+                updates.push(ValueUpdate {
+                    path: name.clone(),
+                    value,
+                    timestamp_us,
+                });
+            }
+            continue;
+        }
+
+        let mut control = Reader::new(payload);
+        match control.take(1).map(|b| b[0]) {
+            Ok(CONTROL_START) => {
+                let entry = control.uint(4)? as u32;
+                let name = control.string()?;
+                let type_name = control.string()?;
+                // DataLogManager and AdvantageScope log NT topics as "NT:/SmartDashboard/...".
+                let path = name.strip_prefix("NT:").map(str::to_string).unwrap_or(name);
+                entries.insert(entry, (path, type_name));
+            }
+            Ok(CONTROL_FINISH) => {
+                entries.remove(&(control.uint(4)? as u32));
+            }
+            // Metadata changes, nothing we use.
+            _ => {}
+        }
+    }
+    Ok(updates)
+}
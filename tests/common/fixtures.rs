@@ -0,0 +1,47 @@
+//! The helpers that don't need the dashboard, so src/nt4_client.rs's unit tests share them too.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::NTValueType;
+
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Polls `done` until it's true or we give up.
+pub fn wait_for(what: &str, mut done: impl FnMut() -> bool) {
+    let start = Instant::now();
+    while !done() {
+        assert!(start.elapsed() < TIMEOUT, "timed out waiting for {}", what);
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+pub fn every_type() -> Vec<(&'static str, NTValueType)> {
+    vec![
+        ("/test/boolean", NTValueType::Boolean(true)),
+        ("/test/double", NTValueType::Double(3.25)),
+        ("/test/float", NTValueType::Float(-1.5)),
+        ("/test/integer", NTValueType::Integer(-8726)),
+        ("/test/string", NTValueType::String(String::from("hello"))),
+        (
+            "/test/booleanArray",
+            NTValueType::BooleanArray(vec![true, false, true]),
+        ),
+        (
+            "/test/doubleArray",
+            NTValueType::DoubleArray(vec![1.0, -2.5, 1e9]),
+        ),
+        ("/test/floatArray", NTValueType::FloatArray(vec![0.5, 2.0])),
+        (
+            "/test/integerArray",
+            NTValueType::IntegerArray(vec![1, -2, i32::MAX]),
+        ),
+        (
+            "/test/stringArray",
+            NTValueType::StringArray(vec![String::from("a"), String::new(), String::from("ünï")]),
+        ),
+        ("/test/emptyArray", NTValueType::DoubleArray(Vec::new())),
+    ]
+}
@@ -0,0 +1,57 @@
+//! Shared by the test files. Not every file uses everything.
+#![allow(dead_code)]
+
+mod fixtures;
+
+pub use fixtures::*;
+
+use egui_kittest::Harness;
+#[cfg(feature = "rust-nt4")]
+use robot_ui_rs::nt4_client::Nt4Client;
+use robot_ui_rs::{FrcUi, data_source::DataSource, nt_instance::NtInstance, nt_util::NTValueType};
+
+/// The dashboard at the size it's usually run, drawing from `source`.
+pub fn harness(source: Box<dyn DataSource>) -> Harness<'static, FrcUi> {
+    let app = FrcUi::with_source(source);
+    Harness::builder()
+        .with_size(egui::vec2(1600.0, 1000.0))
+        .build_state(|ctx, app: &mut FrcUi| app.show(ctx), app)
+}
+
+/// An NT client `connect` can start, ntcore's or ours.
+pub trait TestClient: DataSource + Sized {
+    fn start(name: &str) -> Self;
+    fn connect_to(&mut self, address: &str, port: u32);
+}
+
+impl TestClient for NtInstance {
+    fn start(name: &str) -> Self {
+        NtInstance::start_client(name)
+    }
+
+    fn connect_to(&mut self, address: &str, port: u32) {
+        NtInstance::connect_to(self, address, port);
+    }
+}
+
+#[cfg(feature = "rust-nt4")]
+impl TestClient for Nt4Client {
+    fn start(name: &str) -> Self {
+        Nt4Client::start(name)
+    }
+
+    fn connect_to(&mut self, address: &str, port: u32) {
+        Nt4Client::connect_to(self, address, port);
+    }
+}
+
+/// An ntcore server and a client connected to it. Each test gets its own port so they can run in
+/// parallel.
+pub fn connect<C: TestClient>(port: u32) -> (NtInstance, C) {
+    let persist = std::env::temp_dir().join(format!("robot-ui-rs-test-{}.json", port));
+    let server = NtInstance::start_server(&persist.to_string_lossy(), "127.0.0.1", port);
+    let mut client = C::start("test client");
+    client.connect_to("127.0.0.1", port);
+    wait_for("client to connect", || client.is_connected());
+    (server, client)
+}
@@ -0,0 +1,219 @@
+//! Runs a real ntcore server on localhost and checks values make it through our FFI glue both ways.
+//! Needs the ntcoreffi library like the app does, but no robot.
+
+mod common;
+
+use std::{thread, time::Duration};
+
+use common::{connect, every_type, harness, wait_for};
+use egui::accesskit::Role;
+use egui_kittest::{Harness, kittest::Queryable};
+#[cfg(feature = "rust-nt4")]
+use robot_ui_rs::nt4_client::Nt4Client;
+use robot_ui_rs::{
+    FrcUi, data_source::DataSource, nt_instance::NtInstance, nt_paths, nt_util::NTValueType,
+};
+
+#[test]
+fn robot_values_reach_the_dashboard() {
+    let (mut server, client) = connect::<NtInstance>(5871);
+    for (path, value) in every_type() {
+        server.publish(path, value.clone());
+        wait_for(path, || client.get(path).as_ref() == Some(&value));
+    }
+}
+
+#[test]
+fn dashboard_values_reach_the_robot() {
+    let (server, mut client) = connect::<NtInstance>(5872);
+    for (path, value) in every_type() {
+        client.publish(path, value.clone());
+        wait_for(path, || server.get(path).as_ref() == Some(&value));
+    }
+}
+
+#[test]
+fn missing_topics_read_as_none() {
+    let (_server, client) = connect::<NtInstance>(5873);
+    assert_eq!(client.get("/test/nothingHere"), None);
+    assert_eq!(client.get_double("/test/nothingHere", -1.0), -1.0);
+    assert!(client.get_string_array("/test/nothingHere").is_empty());
+}
+
+#[test]
+fn listeners_get_updates_in_order() {
+    let (mut server, mut client) = connect::<NtInstance>(5874);
+    let path = "/test/counter";
+    server.publish(path, NTValueType::Integer(0));
+    client.listen(path);
+    wait_for("first value", || client.get(path).is_some());
+    // Spaced out since NT4 only sends the latest value each update period (100 ms by default).
+    for i in 1..=5 {
+        server.publish(path, NTValueType::Integer(i));
+        thread::sleep(Duration::from_millis(200));
+    }
+
+    let mut seen = Vec::new();
+    wait_for("all updates", || {
+        seen.extend(
+            client
+                .read_updates()
+                .into_iter()
+                .filter(|u| u.path == path)
+                .map(|u| u.value),
+        );
+        seen.last() == Some(&NTValueType::Integer(5))
+    });
+    let expected: Vec<NTValueType> = (0..=5).map(NTValueType::Integer).collect();
+    assert_eq!(seen, expected);
+}
+
+#[test]
+fn overlapping_listeners_dont_double_updates() {
+    let (mut server, mut client) = connect::<NtInstance>(5880);
+    let path = "/SmartDashboard/Arm/angle";
+    server.publish(path, NTValueType::Double(0.0));
+    // Like logging everything while graphing one topic.
+    client.listen_prefix("/");
+    client.listen_prefix("/SmartDashboard/");
+    client.listen(path);
+    wait_for("first value", || client.get(path).is_some());
+    for i in 1..=3 {
+        server.publish(path, NTValueType::Double(i as f64));
+        thread::sleep(Duration::from_millis(200));
+    }
+
+    let mut seen = Vec::new();
+    wait_for("all updates", || {
+        seen.extend(
+            client
+                .read_updates()
+                .into_iter()
+                .filter(|u| u.path == path)
+                .map(|u| u.value),
+        );
+        seen.last() == Some(&NTValueType::Double(3.0))
+    });
+    let expected: Vec<NTValueType> = (0..=3).map(|i| NTValueType::Double(i as f64)).collect();
+    assert_eq!(seen, expected);
+}
+
+#[test]
+fn listening_again_gets_the_current_value() {
+    let (mut server, mut client) = connect::<NtInstance>(5881);
+    let path = "/SmartDashboard/Arm/angle";
+    server.publish(path, NTValueType::Double(4.0));
+    // Like a graph series being removed and added back.
+    client.listen_prefix("/SmartDashboard/");
+    for _ in 0..2 {
+        let mut seen = Vec::new();
+        wait_for("current value", || {
+            seen.extend(client.read_updates().into_iter().filter(|u| u.path == path));
+            !seen.is_empty()
+        });
+        client.stop_prefix("/SmartDashboard/");
+        client.listen_prefix("/SmartDashboard/");
+    }
+}
+
+#[test]
+fn subscribed_topics_are_listed() {
+    let (mut server, mut client) = connect::<NtInstance>(5875);
+    server.publish(
+        "/SmartDashboard/Arm/.type",
+        NTValueType::String(String::from("Subsystem")),
+    );
+    server.publish("/SmartDashboard/Arm/angle", NTValueType::Double(12.0));
+    client.subscribe("/SmartDashboard/");
+    wait_for("topics", || {
+        let mut topics = client.topics("/SmartDashboard/");
+        topics.sort();
+        topics == ["/SmartDashboard/Arm/.type", "/SmartDashboard/Arm/angle"]
+    });
+}
+
+#[test]
+fn auto_chooser_writes_selection_to_robot() {
+    let (mut server, client) = connect::<NtInstance>(5876);
+    let key = |k| nt_paths::sendable_key(nt_paths::AUTO_CHOOSER, k);
+    server.publish(
+        &key(".type"),
+        NTValueType::String(String::from("String Chooser")),
+    );
+    server.publish(
+        &key("options"),
+        NTValueType::StringArray(vec![String::from("Center"), String::from("Left")]),
+    );
+    server.publish(&key("default"), NTValueType::String(String::from("Center")));
+    server.publish(&key("active"), NTValueType::String(String::from("Center")));
+
+    let mut harness = harness(Box::new(client));
+    let chooser = |harness: &Harness<'_, FrcUi>| {
+        harness
+            .query_by_role_and_label(Role::ComboBox, "Currently selected auto:")
+            .is_some()
+    };
+    wait_for("chooser to show up", || {
+        harness.step();
+        chooser(&harness)
+    });
+
+    harness
+        .get_by_role_and_label(Role::ComboBox, "Currently selected auto:")
+        .click();
+    harness.step();
+    harness.get_by_label("Left").click();
+    harness.step();
+
+    let selected = key("selected");
+    wait_for("selection on the robot", || {
+        server.get(&selected) == Some(NTValueType::String(String::from("Left")))
+    });
+}
+
+#[cfg(feature = "rust-nt4")]
+#[test]
+fn robot_values_reach_the_rust_client() {
+    let (mut server, client) = connect::<Nt4Client>(5877);
+    for (path, value) in every_type() {
+        server.publish(path, value.clone());
+        wait_for(path, || client.get(path).as_ref() == Some(&value));
+    }
+}
+
+#[cfg(feature = "rust-nt4")]
+#[test]
+fn rust_client_values_reach_the_robot() {
+    let (server, mut client) = connect::<Nt4Client>(5878);
+    for (path, value) in every_type() {
+        client.publish(path, value.clone());
+        wait_for(path, || server.get(path).as_ref() == Some(&value));
+    }
+}
+
+#[cfg(feature = "rust-nt4")]
+#[test]
+fn rust_client_listens_and_lists_topics() {
+    let (mut server, mut client) = connect::<Nt4Client>(5879);
+    server.publish("/SmartDashboard/Arm/angle", NTValueType::Double(12.0));
+    client.subscribe("/SmartDashboard/");
+    wait_for("topics", || {
+        client.topics("/SmartDashboard/") == ["/SmartDashboard/Arm/angle"]
+    });
+
+    let path = "/test/counter";
+    server.publish(path, NTValueType::Integer(0));
+    client.listen(path);
+    wait_for("first value", || client.get(path).is_some());
+    for i in 1..=5 {
+        server.publish(path, NTValueType::Integer(i));
+        thread::sleep(Duration::from_millis(200));
+    }
+    let mut seen = Vec::new();
+    wait_for("all updates", || {
+        seen.extend(client.read_updates().into_iter().map(|u| u.value));
+        seen.last() == Some(&NTValueType::Integer(5))
+    });
+    let expected: Vec<NTValueType> = (0..=5).map(NTValueType::Integer).collect();
+    assert_eq!(seen, expected);
+}
@@ -0,0 +1,148 @@
+//! Renders the dashboard headlessly against a MockSource and checks what shows up.
+//! Runs from the crate root, so it picks up field_model.json etc. like the real app does.
+
+mod common;
+
+use egui_kittest::{Harness, kittest::Queryable};
+use robot_ui_rs::{FieldTransform, FrcUi, data_source::MockSource, nt_paths, nt_util::NTValueType};
+
+/// The app requests a repaint every frame, so step a fixed number of frames instead of `run`.
+const FRAMES: usize = 3;
+
+fn harness(nt: &MockSource) -> Harness<'static, FrcUi> {
+    common::harness(Box::new(nt.clone()))
+}
+
+fn step(harness: &mut Harness<'_, FrcUi>) {
+    for _ in 0..FRAMES {
+        harness.step();
+    }
+}
+
+#[test]
+fn shows_unknowns_without_a_robot() {
+    let nt = MockSource::new();
+    let mut harness = harness(&nt);
+    step(&mut harness);
+
+    harness.get_by_label("Current State: Unknown");
+    harness.get_by_label("Lunite Count: Unknown");
+    harness.get_by_label("WAITING FOR MATCH START");
+    harness.get_by_label_contains("no chooser on the robot");
+    // The "Unknown" placeholder isn't a state the robot was in.
+    assert!(harness.query_by_label_contains("isn't in the config").is_none());
+}
+
+#[test]
+fn alliance_label_follows_fms() {
+    let nt = MockSource::new();
+    let mut harness = harness(&nt);
+
+    nt.set(nt_paths::FMS_IS_RED_ALLIANCE, NTValueType::Boolean(true));
+    step(&mut harness);
+    harness.get_by_label("RED ALLIANCE");
+
+    nt.set(nt_paths::FMS_IS_RED_ALLIANCE, NTValueType::Boolean(false));
+    step(&mut harness);
+    harness.get_by_label("BLUE ALLIANCE");
+    assert!(harness.query_by_label("RED ALLIANCE").is_none());
+}
+
+#[test]
+fn shows_game_time_phase_and_state() {
+    let nt = MockSource::new();
+    let mut harness = harness(&nt);
+
+    nt.set(nt_paths::GAME_TIME, NTValueType::Double(92.4));
+    // Enabled, teleop, DS attached.
+    nt.set(nt_paths::FMS_CONTROL_DATA, NTValueType::Integer(0x21));
+    nt.set(
+        nt_paths::CURRENT_STATE,
+        NTValueType::String(String::from("Intaking")),
+    );
+    nt.set(nt_paths::LUNITE_COUNT, NTValueType::Double(3.0));
+    step(&mut harness);
+
+    harness.get_by_label("1:33");
+    harness.get_by_label("TELEOP");
+    harness.get_by_label("Current State: Intaking");
+    harness.get_by_label("Lunite Count: 3");
+}
+
+#[test]
+fn publishes_zone_state_when_robot_enters() {
+    let nt = MockSource::new();
+    let mut harness = harness(&nt);
+    let zone = nt_paths::zone_state("Blue shooting zone");
+
+    // Outside every zone first.
+    nt.set(
+        nt_paths::ROBOT_2D_POSITION,
+        NTValueType::DoubleArray(vec![10.0, 7.0, 0.0]),
+    );
+    step(&mut harness);
+    assert!(matches!(
+        nt.last_published(&zone),
+        Some(NTValueType::Boolean(false))
+    ));
+
+    nt.set(
+        nt_paths::ROBOT_2D_POSITION,
+        NTValueType::DoubleArray(vec![3.0, 4.0, 90.0]),
+    );
+    step(&mut harness);
+    assert!(matches!(
+        nt.last_published(&zone),
+        Some(NTValueType::Boolean(true))
+    ));
+}
+
+/// Field +y is up on screen from blue's side and down from red's. Guards against the lunite
+/// y-flip without needing a GPU like the snapshot below.
+#[test]
+fn field_y_points_up() {
+    let rect = egui::Rect::from_min_size(egui::pos2(100.0, 50.0), egui::vec2(1600.0, 800.0));
+    let blue = FieldTransform {
+        rect,
+        flipped: false,
+    };
+    assert!(blue.to_screen(3.0, 6.0).y < blue.to_screen(3.0, 2.0).y);
+    assert!(blue.to_screen(6.0, 2.0).x > blue.to_screen(3.0, 2.0).x);
+    // The blue origin is the bottom left corner.
+    assert_eq!(blue.to_screen(0.0, 0.0), rect.left_bottom());
+
+    let red = FieldTransform {
+        rect,
+        flipped: true,
+    };
+    assert!(red.to_screen(3.0, 6.0).y > red.to_screen(3.0, 2.0).y);
+    assert_eq!(red.to_screen(0.0, 0.0), rect.right_top());
+}
+
+/// Compares the field with the robot and some lunites on it against tests/snapshots/field_overlay.png.
+/// Needs a wgpu adapter (lavapipe works), so it's behind a feature.
+/// Run with `UPDATE_SNAPSHOTS=1` to (re)generate the image after an intended change.
+#[cfg(feature = "snapshot-tests")]
+#[test]
+fn field_overlay_snapshot() {
+    let nt = MockSource::new();
+    let mut harness = harness(&nt);
+
+    nt.set(nt_paths::FMS_IS_RED_ALLIANCE, NTValueType::Boolean(false));
+    nt.set(
+        nt_paths::ROBOT_2D_POSITION,
+        NTValueType::DoubleArray(vec![3.0, 2.0, 30.0]),
+    );
+    // One lunite near the bottom left and one near the top right, so a flipped axis shows.
+    nt.set(
+        nt_paths::KNOWN_LUNITE_POSITIONS,
+        NTValueType::DoubleArray(vec![1.0, 1.0, 14.0, 7.0]),
+    );
+    nt.set(
+        nt_paths::KNOWN_LUNITE_METADATA,
+        NTValueType::DoubleArray(vec![0.9, 0.5, 1.0, 0.4, 2.0, 2.0]),
+    );
+    step(&mut harness);
+
+    harness.snapshot("field_overlay");
+}
@@ -0,0 +1,31 @@
+//! Reading .wpilog files written by the WPILib tools.
+
+use robot_ui_rs::{
+    nt_util::NTValueType,
+    wpilog::{WpilogWriter, read_wpilog},
+};
+
+/// DataLogManager and AdvantageScope put NT data under "NT:", the panels look for plain paths.
+#[test]
+fn nt_entries_lose_their_prefix() {
+    let mut data = Vec::new();
+    let mut writer = WpilogWriter::new(&mut data, "").unwrap();
+    writer
+        .append(
+            "NT:/SmartDashboard/Lunite Count",
+            &NTValueType::Double(3.0),
+            1000,
+        )
+        .unwrap();
+    writer
+        .append("DS:enabled", &NTValueType::Boolean(true), 2000)
+        .unwrap();
+    drop(writer);
+
+    let paths: Vec<String> = read_wpilog(&data)
+        .unwrap()
+        .into_iter()
+        .map(|u| u.path)
+        .collect();
+    assert_eq!(paths, ["/SmartDashboard/Lunite Count", "DS:enabled"]);
+}